@@ -0,0 +1,368 @@
+//! Generates the `PGN` enum, its metadata table, the per-PGN frame types and
+//! the field decoders from `pgns.json`.
+//!
+//! `pgns.json` follows the layout of canboat's `pgns.json`, trimmed to the
+//! messages this crate knows about. Adding a PGN to the file gives it a `PGN`
+//! variant and, for single frame PGNs, a `<Id>Frame` type and an `NmeaFrame`
+//! variant. Entries that list their `Fields` also get a `<Id>Fields` struct
+//! in `pgn_fields` with a decoder and an encoder, scaled by each field's
+//! `Resolution` and `Offset`, with fields of `FieldType` `LOOKUP`,
+//! `BITLOOKUP` or `BINARY` passed through as raw values, and can be
+//! addressed field by field from group functions. Only numeric fields at a
+//! fixed position are listed; strings and repeating fields are left to the
+//! hand written modules, which add units and enums on top.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+struct FieldDefinition {
+    order: u64,
+    id: String,
+    bit_length: u64,
+    bit_offset: u64,
+    signed: bool,
+    resolution: f64,
+    offset: i64,
+    lookup: bool,
+}
+
+struct PgnDefinition {
+    pgn: u64,
+    id: String,
+    description: String,
+    priority: u64,
+    length: u64,
+    kind: &'static str,
+    transmission_interval: Option<u64>,
+    fields: Vec<FieldDefinition>,
+}
+
+fn kind_from_type(packet_type: &str) -> &'static str {
+    match packet_type {
+        "Single" => "PgnKind::Single",
+        "Fast" => "PgnKind::FastPacket",
+        // canboat marks the ISO 11783 multi packet messages as ISO
+        "ISO" => "PgnKind::TransportProtocol",
+        other => panic!("unknown packet type {}", other),
+    }
+}
+
+fn read_fields(entry: &Value) -> Vec<FieldDefinition> {
+    let Some(fields) = entry["Fields"].as_array() else {
+        return Vec::new();
+    };
+    fields
+        .iter()
+        .map(|field| FieldDefinition {
+            order: field["Order"].as_u64().expect("Order should be a number"),
+            id: field["Id"]
+                .as_str()
+                .expect("field Id should be a string")
+                .to_string(),
+            bit_length: field["BitLength"]
+                .as_u64()
+                .expect("BitLength should be a number"),
+            bit_offset: field["BitOffset"]
+                .as_u64()
+                .expect("BitOffset should be a number"),
+            signed: field["Signed"].as_bool().unwrap_or(false),
+            resolution: field["Resolution"].as_f64().unwrap_or(1.0),
+            offset: field["Offset"].as_i64().unwrap_or(0),
+            lookup: matches!(
+                field["FieldType"].as_str(),
+                Some("LOOKUP" | "BITLOOKUP" | "BINARY")
+            ),
+        })
+        .collect()
+}
+
+fn read_definitions(path: &Path) -> Vec<PgnDefinition> {
+    let source = fs::read_to_string(path).expect("pgns.json should be readable");
+    let root: Value = serde_json::from_str(&source).expect("pgns.json should be valid json");
+    let pgns = root["PGNs"]
+        .as_array()
+        .expect("pgns.json should contain a PGNs array");
+
+    let mut definitions: Vec<PgnDefinition> = pgns
+        .iter()
+        .map(|entry| PgnDefinition {
+            pgn: entry["PGN"].as_u64().expect("PGN should be a number"),
+            id: entry["Id"]
+                .as_str()
+                .expect("Id should be a string")
+                .to_string(),
+            description: entry["Description"]
+                .as_str()
+                .expect("Description should be a string")
+                .to_string(),
+            priority: entry["Priority"]
+                .as_u64()
+                .expect("Priority should be a number"),
+            length: entry["Length"].as_u64().expect("Length should be a number"),
+            kind: kind_from_type(entry["Type"].as_str().expect("Type should be a string")),
+            transmission_interval: entry["TransmissionInterval"].as_u64(),
+            fields: read_fields(entry),
+        })
+        .collect();
+    for definition in &definitions {
+        if definition.kind == "PgnKind::FastPacket" && definition.length > 223 {
+            panic!(
+                "PGN {} is longer than a fast packet message can be",
+                definition.pgn
+            );
+        }
+        for field in &definition.fields {
+            if field.bit_offset + field.bit_length > definition.length * 8 {
+                panic!(
+                    "field {} of PGN {} does not fit in its Length",
+                    field.id, definition.pgn
+                );
+            }
+        }
+    }
+    definitions.sort_by_key(|definition| definition.pgn);
+    definitions
+}
+
+fn generate_pgn_enum(definitions: &[PgnDefinition]) -> String {
+    let mut out = String::new();
+    writeln!(
+        out,
+        "#[derive(FromPrimitive, Debug, PartialEq, Eq, Clone, Copy)]"
+    )
+    .unwrap();
+    writeln!(out, "pub enum PGN {{").unwrap();
+    for definition in definitions {
+        writeln!(out, "    /// {}", definition.description).unwrap();
+        writeln!(out, "    {} = {},", definition.id, definition.pgn).unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+    out
+}
+
+fn generate_pgn_info(definitions: &[PgnDefinition]) -> String {
+    let mut out = String::new();
+    writeln!(out, "static PGN_INFO: [PgnInfo; {}] = [", definitions.len()).unwrap();
+    for definition in definitions {
+        let transmit_interval = match definition.transmission_interval {
+            Some(interval) => format!("Some({})", interval),
+            None => "None".to_string(),
+        };
+        let mut fields = String::new();
+        for field in &definition.fields {
+            write!(
+                fields,
+                "FieldInfo {{ order: {}, id: {:?}, bit_length: {}, bit_offset: {}, signed: {}, resolution: {:?}, offset: {}, lookup: {} }}, ",
+                field.order,
+                field.id,
+                field.bit_length,
+                field.bit_offset,
+                field.signed,
+                field.resolution,
+                field.offset,
+                field.lookup
+            )
+            .unwrap();
+        }
+        writeln!(
+            out,
+            "    PgnInfo {{ pgn: {}, name: {:?}, priority: {}, length: {}, kind: {}, transmit_interval: {}, fields: &[{}] }},",
+            definition.pgn,
+            definition.description,
+            definition.priority,
+            definition.length,
+            definition.kind,
+            transmit_interval,
+            fields
+        )
+        .unwrap();
+    }
+    writeln!(out, "];").unwrap();
+    out
+}
+
+fn generate_frames(definitions: &[PgnDefinition]) -> String {
+    let singles: Vec<&PgnDefinition> = definitions
+        .iter()
+        .filter(|definition| definition.kind == "PgnKind::Single")
+        .collect();
+    let mut out = String::new();
+    for definition in &singles {
+        writeln!(out, "pub struct {}Frame {{", definition.id).unwrap();
+        writeln!(out, "    pub data: [u8; 8],").unwrap();
+        writeln!(out, "}}").unwrap();
+    }
+
+    writeln!(out, "pub enum NmeaFrame {{").unwrap();
+    for definition in &singles {
+        writeln!(out, "    {0}({0}Frame),", definition.id).unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+
+    writeln!(out, "impl NmeaFrame {{").unwrap();
+    writeln!(
+        out,
+        "    pub fn new(pgn: PGN, data: [u8; 8]) -> Option<NmeaFrame> {{"
+    )
+    .unwrap();
+    writeln!(out, "        match pgn {{").unwrap();
+    for definition in &singles {
+        writeln!(
+            out,
+            "            PGN::{0} => Some(NmeaFrame::{0}({0}Frame {{ data }})),",
+            definition.id
+        )
+        .unwrap();
+    }
+    writeln!(out, "            _ => None,").unwrap();
+    writeln!(out, "        }}").unwrap();
+    writeln!(out, "    }}").unwrap();
+    writeln!(out, "}}").unwrap();
+    out
+}
+
+/// `targetHeadingTrue` becomes `target_heading_true`.
+fn field_name(id: &str) -> String {
+    let mut name = String::new();
+    for c in id.chars() {
+        if c.is_ascii_uppercase() {
+            name.push('_');
+        }
+        name.push(c.to_ascii_lowercase());
+    }
+    match name.as_str() {
+        "type" | "match" | "mod" | "ref" | "move" => format!("r#{}", name),
+        _ => name,
+    }
+}
+
+fn generate_field_structs(definitions: &[PgnDefinition]) -> String {
+    let mut out = String::new();
+    for definition in definitions {
+        let fields: Vec<(usize, String)> = definition
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| field.id != "reserved")
+            .map(|(index, field)| (index, field_name(&field.id)))
+            .collect();
+        if fields.is_empty() {
+            continue;
+        }
+
+        writeln!(
+            out,
+            "/// PGN {}, {}.",
+            definition.pgn, definition.description
+        )
+        .unwrap();
+        writeln!(out, "#[derive(Debug, PartialEq, Clone, Copy)]").unwrap();
+        writeln!(out, "pub struct {}Fields {{", definition.id).unwrap();
+        for (_, name) in &fields {
+            writeln!(out, "    pub {}: FieldValue<f64>,", name).unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+
+        writeln!(
+            out,
+            "impl Message<{0}Fields, &[u8]> for {0}Fields {{",
+            definition.id
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "    fn get_data(data: &[u8]) -> Result<{}Fields, NmeaError> {{",
+            definition.id
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "        let fields = PGN::{}.info().fields;",
+            definition.id
+        )
+        .unwrap();
+        writeln!(out, "        Ok({}Fields {{", definition.id).unwrap();
+        for (index, name) in &fields {
+            writeln!(
+                out,
+                "            {}: fields[{}].read(data).ok_or(NmeaError::ParseError)?,",
+                name, index
+            )
+            .unwrap();
+        }
+        writeln!(out, "        }})").unwrap();
+        writeln!(out, "    }}").unwrap();
+        writeln!(out, "}}").unwrap();
+
+        writeln!(out, "impl Encode<Vec<u8>> for {}Fields {{", definition.id).unwrap();
+        writeln!(out, "    fn encode(&self) -> Vec<u8> {{").unwrap();
+        writeln!(out, "        let info = PGN::{}.info();", definition.id).unwrap();
+        writeln!(out, "        let mut data = info.empty_payload();").unwrap();
+        for (index, name) in &fields {
+            writeln!(
+                out,
+                "        info.fields[{}].write(&mut data, self.{});",
+                index, name
+            )
+            .unwrap();
+        }
+        writeln!(out, "        data").unwrap();
+        writeln!(out, "    }}").unwrap();
+        writeln!(out, "}}").unwrap();
+
+        if definition.kind == "PgnKind::Single" {
+            writeln!(out, "impl {}Fields {{", definition.id).unwrap();
+            writeln!(
+                out,
+                "    pub fn to_frame(&self) -> crate::nmea_frame::{}Frame {{",
+                definition.id
+            )
+            .unwrap();
+            writeln!(out, "        let mut data = [0xFF; 8];").unwrap();
+            writeln!(out, "        data.copy_from_slice(&self.encode()[..8]);").unwrap();
+            writeln!(
+                out,
+                "        crate::nmea_frame::{}Frame {{ data }}",
+                definition.id
+            )
+            .unwrap();
+            writeln!(out, "    }}").unwrap();
+            writeln!(out, "}}").unwrap();
+        }
+    }
+    out
+}
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let pgns_path = Path::new(&manifest_dir).join("pgns.json");
+    println!("cargo:rerun-if-changed={}", pgns_path.display());
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let definitions = read_definitions(&pgns_path);
+    fs::write(
+        Path::new(&out_dir).join("pgn.rs"),
+        generate_pgn_enum(&definitions),
+    )
+    .unwrap();
+    fs::write(
+        Path::new(&out_dir).join("pgn_info.rs"),
+        generate_pgn_info(&definitions),
+    )
+    .unwrap();
+    fs::write(
+        Path::new(&out_dir).join("nmea_frame.rs"),
+        generate_frames(&definitions),
+    )
+    .unwrap();
+    fs::write(
+        Path::new(&out_dir).join("pgn_fields.rs"),
+        generate_field_structs(&definitions),
+    )
+    .unwrap();
+}
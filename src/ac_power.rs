@@ -0,0 +1,359 @@
+use nom::{multi::count, IResult};
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use crate::{
+    field_value::FieldValue,
+    parse::{take_byte, take_u16, take_u32, BitInput},
+    pgn::PGN,
+    pgn_fields::{
+        Bus1PhaseCBasicACQuantitiesFields, UtilityTotalACEnergyFields, UtilityTotalACPowerFields,
+        UtilityTotalACReactivePowerFields,
+    },
+    units::{Current, Frequency, Power, Voltage},
+    Message, NmeaError,
+};
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum ACLine {
+    Line1 = 0,
+    Line2 = 1,
+    Line3 = 2,
+}
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum ACAcceptability {
+    BadLevel = 0,
+    BadFrequency = 1,
+    BeingQualified = 2,
+    Good = 3,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ACLineStatus {
+    pub line: Option<ACLine>,
+    pub acceptability: Option<ACAcceptability>,
+    pub voltage: FieldValue<Voltage>,
+    pub current: FieldValue<Current>,
+    pub frequency: FieldValue<Frequency>,
+    pub breaker_size: FieldValue<Current>,
+    pub real_power: FieldValue<Power>,
+    pub reactive_power: FieldValue<Power>,
+    pub power_factor: FieldValue<f32>,
+}
+
+fn parse_ac_line_status(i: BitInput) -> IResult<BitInput, ACLineStatus> {
+    let (i, line) = take_byte(i)?;
+    let (i, voltage) = take_u16(i)?;
+    let (i, current) = take_u16(i)?;
+    let (i, frequency) = take_u16(i)?;
+    let (i, breaker_size) = take_u16(i)?;
+    let (i, real_power) = take_u32(i)?;
+    let (i, reactive_power) = take_u32(i)?;
+    let (i, power_factor) = take_byte(i)?;
+
+    let line_status = ACLineStatus {
+        line: FromPrimitive::from_u8(line & 0x03),
+        acceptability: FromPrimitive::from_u8((line >> 2) & 0x03),
+        voltage: FieldValue::from_u16(voltage)
+            .map(|voltage| Voltage::from_volts(voltage as f32 * 0.01)),
+        current: FieldValue::from_u16(current)
+            .map(|current| Current::from_amperes(current as f32 * 0.1)),
+        frequency: FieldValue::from_u16(frequency)
+            .map(|frequency| Frequency::from_hertz(frequency as f32 * 0.01)),
+        breaker_size: FieldValue::from_u16(breaker_size)
+            .map(|breaker_size| Current::from_amperes(breaker_size as f32 * 0.1)),
+        real_power: FieldValue::from_u32(real_power)
+            .map(|real_power| Power::from_watts(real_power as f32)),
+        reactive_power: FieldValue::from_u32(reactive_power)
+            .map(|reactive_power| Power::from_watts(reactive_power as f32)),
+        power_factor: FieldValue::from_u8(power_factor)
+            .map(|power_factor| power_factor as f32 * 0.01),
+    };
+    Ok((i, line_status))
+}
+
+/// PGN 127503 AC Input Status and 127504 AC Output Status, fast packet
+/// messages with one entry per line.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ACStatus {
+    pub instance: u8,
+    pub lines: Vec<ACLineStatus>,
+}
+
+fn parse_ac_status(i: BitInput) -> IResult<BitInput, ACStatus> {
+    let (i, instance) = take_byte(i)?;
+    let (i, number_of_lines) = take_byte(i)?;
+    let (i, lines) = count(parse_ac_line_status, number_of_lines as usize)(i)?;
+    Ok((i, ACStatus { instance, lines }))
+}
+
+impl ACStatus {
+    pub fn get_line(&self, line: ACLine) -> Option<&ACLineStatus> {
+        self.lines.iter().find(|status| status.line == Some(line))
+    }
+}
+
+impl Message<ACStatus, &[u8]> for ACStatus {
+    fn get_data(data: &[u8]) -> Result<ACStatus, NmeaError> {
+        let parse_result: IResult<&[u8], ACStatus> = nom::bits::bits(parse_ac_status)(data);
+        match parse_result {
+            Ok((_, ac_status)) => Ok(ac_status),
+            Err(_e) => Err(NmeaError::ParseError),
+        }
+    }
+}
+
+/// Voltage, frequency and current of one phase, or their average, from the
+/// J1939 "Basic AC Quantities" PGNs.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ACBasicQuantities {
+    pub line_line_voltage: FieldValue<Voltage>,
+    pub line_neutral_voltage: FieldValue<Voltage>,
+    pub frequency: FieldValue<Frequency>,
+    /// Not sent for bus measurements.
+    pub current: FieldValue<Current>,
+}
+
+// Every generator, utility and bus PGN of one kind shares a layout, so one
+// representative PGN's fields decode them all.
+impl Message<ACBasicQuantities, &[u8]> for ACBasicQuantities {
+    fn get_data(data: &[u8]) -> Result<ACBasicQuantities, NmeaError> {
+        let fields = Bus1PhaseCBasicACQuantitiesFields::get_data(data)?;
+        Ok(ACBasicQuantities {
+            line_line_voltage: fields
+                .line_line_ac_rms_voltage
+                .map(|voltage| Voltage::from_volts(voltage as f32)),
+            line_neutral_voltage: fields
+                .line_neutral_ac_rms_voltage
+                .map(|voltage| Voltage::from_volts(voltage as f32)),
+            frequency: fields
+                .ac_frequency
+                .map(|frequency| Frequency::from_hertz(frequency as f32)),
+            current: fields
+                .ac_rms_current
+                .map(|current| Current::from_amperes(current as f32)),
+        })
+    }
+}
+
+/// J1939 "AC Power" PGNs. Negative power flows back into the source.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ACPower {
+    pub real_power: FieldValue<Power>,
+    pub apparent_power: FieldValue<Power>,
+}
+
+impl Message<ACPower, &[u8]> for ACPower {
+    fn get_data(data: &[u8]) -> Result<ACPower, NmeaError> {
+        let fields = UtilityTotalACPowerFields::get_data(data)?;
+        Ok(ACPower {
+            real_power: fields
+                .real_power
+                .map(|power| Power::from_watts(power as f32)),
+            apparent_power: fields
+                .apparent_power
+                .map(|power| Power::from_watts(power as f32)),
+        })
+    }
+}
+
+/// J1939 "AC Reactive Power" PGNs.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ACReactivePower {
+    pub reactive_power: FieldValue<Power>,
+    /// From -1 to 1.
+    pub power_factor: FieldValue<f32>,
+    /// True when current lags voltage, false when it leads.
+    pub power_factor_lagging: FieldValue<bool>,
+}
+
+impl Message<ACReactivePower, &[u8]> for ACReactivePower {
+    fn get_data(data: &[u8]) -> Result<ACReactivePower, NmeaError> {
+        let fields = UtilityTotalACReactivePowerFields::get_data(data)?;
+        Ok(ACReactivePower {
+            reactive_power: fields
+                .reactive_power
+                .map(|power| Power::from_watts(power as f32)),
+            power_factor: fields.power_factor.map(|power_factor| power_factor as f32),
+            power_factor_lagging: fields.power_factor_lagging.to_status(),
+        })
+    }
+}
+
+/// J1939 "Total AC Energy" PGNs.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ACTotalEnergy {
+    /// Kilowatt hours
+    pub exported: FieldValue<u32>,
+    /// Kilowatt hours
+    pub imported: FieldValue<u32>,
+}
+
+impl Message<ACTotalEnergy, &[u8]> for ACTotalEnergy {
+    fn get_data(data: &[u8]) -> Result<ACTotalEnergy, NmeaError> {
+        let fields = UtilityTotalACEnergyFields::get_data(data)?;
+        Ok(ACTotalEnergy {
+            exported: fields.total_energy_export.map(|energy| energy as u32),
+            imported: fields.total_energy_import.map(|energy| energy as u32),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ACSource {
+    Generator,
+    Utility,
+    Bus1,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ACPhase {
+    A,
+    B,
+    C,
+    Average,
+    Total,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ACQuantities {
+    Basic(ACBasicQuantities),
+    Power(ACPower),
+    ReactivePower(ACReactivePower),
+    Energy(ACTotalEnergy),
+}
+
+/// Any of the J1939 generator, utility and bus PGNs 65001 to 65030.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ACPhaseData {
+    pub source: ACSource,
+    pub phase: ACPhase,
+    pub quantities: ACQuantities,
+}
+
+impl ACPhaseData {
+    /// Decodes `data` by what `pgn` carries. Other PGNs are `NotImplemented`.
+    pub fn decode(pgn: PGN, data: &[u8]) -> Result<ACPhaseData, NmeaError> {
+        use ACPhase::*;
+        use ACSource::*;
+
+        let basic = || ACBasicQuantities::get_data(data).map(ACQuantities::Basic);
+        let power = || ACPower::get_data(data).map(ACQuantities::Power);
+        let reactive = || ACReactivePower::get_data(data).map(ACQuantities::ReactivePower);
+        let energy = || ACTotalEnergy::get_data(data).map(ACQuantities::Energy);
+
+        let (source, phase, quantities) = match pgn {
+            PGN::Bus1PhaseCBasicACQuantities => (Bus1, C, basic()?),
+            PGN::Bus1PhaseBBasicACQuantities => (Bus1, B, basic()?),
+            PGN::Bus1PhaseABasicACQuantities => (Bus1, A, basic()?),
+            PGN::Bus1AverageBasicACQuantities => (Bus1, Average, basic()?),
+            PGN::UtilityTotalACEnergy => (Utility, Total, energy()?),
+            PGN::UtilityPhaseCACReactivePower => (Utility, C, reactive()?),
+            PGN::UtilityPhaseCACPower => (Utility, C, power()?),
+            PGN::UtilityPhaseCBasicACQuantities => (Utility, C, basic()?),
+            PGN::UtilityPhaseBACReactivePower => (Utility, B, reactive()?),
+            PGN::UtilityPhaseBACPower => (Utility, B, power()?),
+            PGN::UtilityPhaseBBasicACQuantities => (Utility, B, basic()?),
+            PGN::UtilityPhaseAACReactivePower => (Utility, A, reactive()?),
+            PGN::UtilityPhaseAACPower => (Utility, A, power()?),
+            PGN::UtilityPhaseABasicACQuantities => (Utility, A, basic()?),
+            PGN::UtilityTotalACReactivePower => (Utility, Total, reactive()?),
+            PGN::UtilityTotalACPower => (Utility, Total, power()?),
+            PGN::UtilityAverageBasicACQuantities => (Utility, Average, basic()?),
+            PGN::GeneratorTotalACEnergy => (Generator, Total, energy()?),
+            PGN::GeneratorPhaseCACReactivePower => (Generator, C, reactive()?),
+            PGN::GeneratorPhaseCACPower => (Generator, C, power()?),
+            PGN::GeneratorPhaseCBasicACQuantities => (Generator, C, basic()?),
+            PGN::GeneratorPhaseBACReactivePower => (Generator, B, reactive()?),
+            PGN::GeneratorPhaseBACPower => (Generator, B, power()?),
+            PGN::GeneratorPhaseBBasicACQuantities => (Generator, B, basic()?),
+            PGN::GeneratorPhaseAACReactivePower => (Generator, A, reactive()?),
+            PGN::GeneratorPhaseAACPower => (Generator, A, power()?),
+            PGN::GeneratorPhaseABasicACQuantities => (Generator, A, basic()?),
+            PGN::GeneratorTotalACReactivePower => (Generator, Total, reactive()?),
+            PGN::GeneratorTotalACPower => (Generator, Total, power()?),
+            PGN::GeneratorAverageBasicACQuantities => (Generator, Average, basic()?),
+            _ => return Err(NmeaError::NotImplemented),
+        };
+        Ok(ACPhaseData {
+            source,
+            phase,
+            quantities,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ac_input_status() {
+        let data = [
+            0x01, 0x01, 0xFC, 0xD8, 0x59, 0x7D, 0x00, 0x88, 0x13, 0xA0, 0x00, 0xF0, 0x0A, 0x00,
+            0x00, 0x2C, 0x01, 0x00, 0x00, 0x5F,
+        ];
+        let status = ACStatus::get_data(&data).unwrap();
+        assert_eq!(1, status.instance);
+        let line = status.get_line(ACLine::Line1).unwrap();
+        assert_eq!(Some(ACAcceptability::Good), line.acceptability);
+        assert_eq!(230.0, line.voltage.value().unwrap().get_volts());
+        assert_eq!(12.5, line.current.value().unwrap().get_amperes());
+        assert_eq!(50.0, line.frequency.value().unwrap().get_hertz());
+        assert_eq!(16.0, line.breaker_size.value().unwrap().get_amperes());
+        assert_eq!(2800.0, line.real_power.value().unwrap().get_watts());
+        assert_eq!(300.0, line.reactive_power.value().unwrap().get_watts());
+        assert_eq!(None, status.get_line(ACLine::Line2));
+
+        assert!(ACStatus::get_data(&[0x01, 0x02]).is_err());
+    }
+
+    #[test]
+    fn decodes_generator_phase_data() {
+        let basic = [0x90, 0x01, 0xE6, 0x00, 0x00, 0x19, 0x14, 0x00];
+        let data = ACPhaseData::decode(PGN::GeneratorPhaseABasicACQuantities, &basic).unwrap();
+        assert_eq!(ACSource::Generator, data.source);
+        assert_eq!(ACPhase::A, data.phase);
+        match data.quantities {
+            ACQuantities::Basic(basic) => {
+                assert_eq!(400.0, basic.line_line_voltage.value().unwrap().get_volts());
+                assert_eq!(
+                    230.0,
+                    basic.line_neutral_voltage.value().unwrap().get_volts()
+                );
+                assert_eq!(50.0, basic.frequency.value().unwrap().get_hertz());
+                assert_eq!(20.0, basic.current.value().unwrap().get_amperes());
+            }
+            _ => panic!("expected basic quantities"),
+        }
+
+        let power = [0xD0, 0x9B, 0x35, 0x77, 0xC4, 0x9D, 0x35, 0x77];
+        let data = ACPhaseData::decode(PGN::GeneratorTotalACPower, &power).unwrap();
+        assert_eq!(ACPhase::Total, data.phase);
+        match data.quantities {
+            ACQuantities::Power(power) => {
+                assert_eq!(2000.0, power.real_power.value().unwrap().get_watts());
+                assert_eq!(2500.0, power.apparent_power.value().unwrap().get_watts());
+            }
+            _ => panic!("expected power"),
+        }
+
+        let reactive = [0x0C, 0x92, 0x35, 0x77, 0x00, 0x60, 0xFD, 0xFF];
+        let data = ACPhaseData::decode(PGN::UtilityTotalACReactivePower, &reactive).unwrap();
+        assert_eq!(ACSource::Utility, data.source);
+        match data.quantities {
+            ACQuantities::ReactivePower(reactive) => {
+                assert_eq!(-500.0, reactive.reactive_power.value().unwrap().get_watts());
+                assert_eq!(FieldValue::Value(0.5), reactive.power_factor);
+                assert_eq!(FieldValue::Value(true), reactive.power_factor_lagging);
+            }
+            _ => panic!("expected reactive power"),
+        }
+
+        assert!(matches!(
+            ACPhaseData::decode(PGN::VesselHeading, &basic),
+            Err(NmeaError::NotImplemented)
+        ));
+    }
+}
@@ -0,0 +1,697 @@
+use bitfield::bitfield;
+use nom::sequence::pair;
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use crate::{
+    date::{Date, DateTime, Time},
+    field_value::FieldValue,
+    parse::{parse_at, take_fixed_string, take_lau_string},
+    pgn_fields::{
+        AISAidsToNavigationReportFields, AISClassAPositionReportFields, AISClassAStaticDataFields,
+        AISClassBPositionReportFields, AISClassBStaticDataPartAFields,
+        AISClassBStaticDataPartBFields,
+    },
+    rad::Rad,
+    units::{AngularRate, Distance, Latitude, Longitude, Speed},
+    Message, NmeaError,
+};
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum AISTransceiver {
+    ChannelAReception = 0,
+    ChannelBReception = 1,
+    ChannelATransmission = 2,
+    ChannelBTransmission = 3,
+    OwnInformationNotBroadcast = 4,
+}
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum NavigationalStatus {
+    UnderWayUsingEngine = 0,
+    AtAnchor = 1,
+    NotUnderCommand = 2,
+    RestrictedManoeuverability = 3,
+    ConstrainedByDraught = 4,
+    Moored = 5,
+    Aground = 6,
+    EngagedInFishing = 7,
+    UnderWaySailing = 8,
+    HazardousMaterialHighSpeed = 9,
+    HazardousMaterialWingInGround = 10,
+    TowingAstern = 11,
+    PushingAheadOrTowingAlongside = 12,
+    AISSart = 14,
+}
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum SpecialManeuver {
+    NotEngaged = 1,
+    Engaged = 2,
+}
+
+/// Second of the UTC minute the report was generated, or why it is missing.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum AISTimeStamp {
+    Second(u8),
+    NotAvailable,
+    ManualInput,
+    DeadReckoning,
+    Inoperative,
+}
+
+impl AISTimeStamp {
+    fn from_raw(raw: u8) -> AISTimeStamp {
+        match raw {
+            0..=59 => AISTimeStamp::Second(raw),
+            61 => AISTimeStamp::ManualInput,
+            62 => AISTimeStamp::DeadReckoning,
+            63 => AISTimeStamp::Inoperative,
+            _ => AISTimeStamp::NotAvailable,
+        }
+    }
+}
+
+bitfield! {
+    /// Capabilities of a Class B unit, sent with every position report.
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct ClassBUnitFlags(u16);
+    impl Debug;
+    /// Carrier sense unit when set, SOTDMA otherwise.
+    pub carrier_sense, _: 2;
+    pub integrated_display, _: 3;
+    pub dsc, _: 4;
+    /// Uses the whole marine band when set, the upper 525 kHz otherwise.
+    pub whole_marine_band, _: 5;
+    pub can_handle_message_22, _: 6;
+    /// Assigned mode when set, autonomous otherwise.
+    pub assigned_mode, _: 7;
+    /// ITDMA communication state when set, SOTDMA otherwise.
+    pub itdma_communication_state, _: 8;
+}
+
+fn message_id(message_id: FieldValue<f64>) -> u8 {
+    message_id
+        .value()
+        .map_or(0x3F, |message_id| message_id as u8)
+}
+
+fn repeat_indicator(repeat_indicator: FieldValue<f64>) -> u8 {
+    repeat_indicator.value().map_or(3, |repeat| repeat as u8)
+}
+
+fn mmsi(user_id: FieldValue<f64>) -> u32 {
+    user_id.value().map_or(u32::MAX, |mmsi| mmsi as u32)
+}
+
+fn is_set(flag: FieldValue<f64>) -> bool {
+    flag == FieldValue::Value(1.0)
+}
+
+fn time_stamp(time_stamp: FieldValue<f64>) -> AISTimeStamp {
+    AISTimeStamp::from_raw(time_stamp.value().map_or(63, |second| second as u8))
+}
+
+fn angle(radians: FieldValue<f64>) -> FieldValue<Rad> {
+    radians.map(|radians| Rad::from_radians(radians as f32))
+}
+
+/// Fields shared by Class A and Class B position reports.
+struct PositionFields {
+    message_id: u8,
+    repeat_indicator: u8,
+    mmsi: u32,
+    longitude: FieldValue<Longitude>,
+    latitude: FieldValue<Latitude>,
+    high_position_accuracy: bool,
+    raim: bool,
+    time_stamp: AISTimeStamp,
+    cog: FieldValue<Rad>,
+    sog: FieldValue<Speed>,
+    communication_state: u32,
+    transceiver: Option<AISTransceiver>,
+    heading: FieldValue<Rad>,
+}
+
+/// Class A and Class B position reports lay out their shared fields the
+/// same way, so either's fields decode into `PositionFields`.
+macro_rules! position_fields {
+    ($fields:expr) => {
+        PositionFields {
+            message_id: message_id($fields.message_id),
+            repeat_indicator: repeat_indicator($fields.repeat_indicator),
+            mmsi: mmsi($fields.user_id),
+            longitude: $fields.longitude.map(Longitude::from_degrees),
+            latitude: $fields.latitude.map(Latitude::from_degrees),
+            high_position_accuracy: is_set($fields.position_accuracy),
+            raim: is_set($fields.raim),
+            time_stamp: time_stamp($fields.time_stamp),
+            cog: angle($fields.cog),
+            sog: $fields
+                .sog
+                .map(|sog| Speed::from_meters_per_second(sog as f32)),
+            communication_state: $fields
+                .communication_state
+                .value()
+                .map_or(0x7FFFF, |state| state as u32),
+            transceiver: $fields
+                .ais_transceiver_information
+                .value()
+                .and_then(FromPrimitive::from_f64),
+            heading: angle($fields.heading),
+        }
+    };
+}
+
+/// PGN 129038, a fast packet message.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct AISClassAPositionReport {
+    /// AIS message 1, 2 or 3.
+    pub message_id: u8,
+    pub repeat_indicator: u8,
+    pub mmsi: u32,
+    pub longitude: FieldValue<Longitude>,
+    pub latitude: FieldValue<Latitude>,
+    /// Better than 10 m when set.
+    pub high_position_accuracy: bool,
+    pub raim: bool,
+    pub time_stamp: AISTimeStamp,
+    pub cog: FieldValue<Rad>,
+    pub sog: FieldValue<Speed>,
+    pub communication_state: u32,
+    pub transceiver: Option<AISTransceiver>,
+    pub heading: FieldValue<Rad>,
+    pub rate_of_turn: FieldValue<AngularRate>,
+    pub navigational_status: Option<NavigationalStatus>,
+    pub special_maneuver: Option<SpecialManeuver>,
+    pub sequence_id: FieldValue<u8>,
+}
+
+impl Message<AISClassAPositionReport, &[u8]> for AISClassAPositionReport {
+    fn get_data(data: &[u8]) -> Result<AISClassAPositionReport, NmeaError> {
+        let fields = AISClassAPositionReportFields::get_data(data)?;
+        let position = position_fields!(fields);
+        Ok(AISClassAPositionReport {
+            message_id: position.message_id,
+            repeat_indicator: position.repeat_indicator,
+            mmsi: position.mmsi,
+            longitude: position.longitude,
+            latitude: position.latitude,
+            high_position_accuracy: position.high_position_accuracy,
+            raim: position.raim,
+            time_stamp: position.time_stamp,
+            cog: position.cog,
+            sog: position.sog,
+            communication_state: position.communication_state,
+            transceiver: position.transceiver,
+            heading: position.heading,
+            rate_of_turn: fields
+                .rate_of_turn
+                .map(AngularRate::from_radians_per_second),
+            navigational_status: fields.nav_status.value().and_then(FromPrimitive::from_f64),
+            special_maneuver: fields
+                .special_maneuver_indicator
+                .value()
+                .and_then(FromPrimitive::from_f64),
+            sequence_id: fields.sequence_id.map(|sequence_id| sequence_id as u8),
+        })
+    }
+}
+
+/// PGN 129039, a fast packet message.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct AISClassBPositionReport {
+    /// AIS message 18.
+    pub message_id: u8,
+    pub repeat_indicator: u8,
+    pub mmsi: u32,
+    pub longitude: FieldValue<Longitude>,
+    pub latitude: FieldValue<Latitude>,
+    /// Better than 10 m when set.
+    pub high_position_accuracy: bool,
+    pub raim: bool,
+    pub time_stamp: AISTimeStamp,
+    pub cog: FieldValue<Rad>,
+    pub sog: FieldValue<Speed>,
+    pub communication_state: u32,
+    pub transceiver: Option<AISTransceiver>,
+    pub heading: FieldValue<Rad>,
+    pub unit_flags: ClassBUnitFlags,
+}
+
+impl Message<AISClassBPositionReport, &[u8]> for AISClassBPositionReport {
+    fn get_data(data: &[u8]) -> Result<AISClassBPositionReport, NmeaError> {
+        let fields = AISClassBPositionReportFields::get_data(data)?;
+        let position = position_fields!(fields);
+        let flags = [
+            fields.unit_type,
+            fields.integrated_display,
+            fields.dsc,
+            fields.band,
+            fields.can_handle_msg22,
+            fields.ais_mode,
+            fields.ais_communication_state,
+        ];
+        // The flags start at bit 2 of `ClassBUnitFlags`
+        let unit_flags = flags
+            .iter()
+            .enumerate()
+            .filter(|(_, flag)| is_set(**flag))
+            .fold(0, |unit_flags, (bit, _)| unit_flags | 1 << (bit + 2));
+        Ok(AISClassBPositionReport {
+            message_id: position.message_id,
+            repeat_indicator: position.repeat_indicator,
+            mmsi: position.mmsi,
+            longitude: position.longitude,
+            latitude: position.latitude,
+            high_position_accuracy: position.high_position_accuracy,
+            raim: position.raim,
+            time_stamp: position.time_stamp,
+            cog: position.cog,
+            sog: position.sog,
+            communication_state: position.communication_state,
+            transceiver: position.transceiver,
+            heading: position.heading,
+            unit_flags: ClassBUnitFlags(unit_flags),
+        })
+    }
+}
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum PositionFixingDevice {
+    Undefined = 0,
+    GPS = 1,
+    GLONASS = 2,
+    GPSGLONASS = 3,
+    LoranC = 4,
+    Chayka = 5,
+    IntegratedNavigationSystem = 6,
+    Surveyed = 7,
+    Galileo = 8,
+    InternalGNSS = 15,
+}
+
+/// Edition of ITU-R M.1371 the transponder implements.
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum AISVersion {
+    Edition1 = 0,
+    Edition3 = 1,
+    Edition5 = 2,
+}
+
+/// Size of a vessel or aid to navigation, and where its position reference
+/// point sits within it.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct AISDimensions {
+    pub length: FieldValue<Distance>,
+    pub beam: FieldValue<Distance>,
+    pub position_reference_from_starboard: FieldValue<Distance>,
+    pub position_reference_from_bow: FieldValue<Distance>,
+}
+
+fn dimensions(
+    length: FieldValue<f64>,
+    beam: FieldValue<f64>,
+    position_reference_from_starboard: FieldValue<f64>,
+    position_reference_from_bow: FieldValue<f64>,
+) -> AISDimensions {
+    AISDimensions {
+        length: length.map(Distance::from_meters),
+        beam: beam.map(Distance::from_meters),
+        position_reference_from_starboard: position_reference_from_starboard
+            .map(Distance::from_meters),
+        position_reference_from_bow: position_reference_from_bow.map(Distance::from_meters),
+    }
+}
+
+/// PGN 129794, a fast packet message.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AISClassAStaticData {
+    /// AIS message 5.
+    pub message_id: u8,
+    pub repeat_indicator: u8,
+    pub mmsi: u32,
+    pub imo_number: FieldValue<u32>,
+    pub callsign: String,
+    pub name: String,
+    /// ITU-R M.1371 type of ship and cargo.
+    pub ship_type: FieldValue<u8>,
+    pub dimensions: AISDimensions,
+    pub eta_date: FieldValue<Date>,
+    pub eta_time: FieldValue<Time>,
+    pub draught: FieldValue<Distance>,
+    pub destination: String,
+    pub ais_version: Option<AISVersion>,
+    pub position_fixing_device: Option<PositionFixingDevice>,
+    /// Data terminal equipment ready to receive text messages.
+    pub dte_available: bool,
+    pub transceiver: Option<AISTransceiver>,
+}
+
+impl AISClassAStaticData {
+    pub fn get_eta(&self) -> Option<DateTime> {
+        Some(DateTime {
+            date: self.eta_date.value()?,
+            time: self.eta_time.value()?,
+        })
+    }
+}
+
+impl Message<AISClassAStaticData, &[u8]> for AISClassAStaticData {
+    fn get_data(data: &[u8]) -> Result<AISClassAStaticData, NmeaError> {
+        let fields = AISClassAStaticDataFields::get_data(data)?;
+        let (callsign, name) =
+            parse_at(data, 9, pair(take_fixed_string(7), take_fixed_string(20)))?;
+        let destination = parse_at(data, 53, take_fixed_string(20))?;
+        Ok(AISClassAStaticData {
+            message_id: message_id(fields.message_id),
+            repeat_indicator: repeat_indicator(fields.repeat_indicator),
+            mmsi: mmsi(fields.user_id),
+            imo_number: fields.imo_number.map(|imo_number| imo_number as u32),
+            callsign,
+            name,
+            ship_type: fields.type_of_ship.map(|ship_type| ship_type as u8),
+            dimensions: dimensions(
+                fields.length,
+                fields.beam,
+                fields.position_reference_from_starboard,
+                fields.position_reference_from_bow,
+            ),
+            eta_date: fields.eta_date.map(|days| Date::new(days as u16)),
+            eta_time: fields.eta_time.map(Time::from_seconds),
+            draught: fields.draft.map(Distance::from_meters),
+            destination,
+            ais_version: fields
+                .ais_version_indicator
+                .value()
+                .and_then(FromPrimitive::from_f64),
+            position_fixing_device: fields.gnss_type.value().and_then(FromPrimitive::from_f64),
+            dte_available: fields.dte == FieldValue::Value(0.0),
+            transceiver: fields
+                .ais_transceiver_information
+                .value()
+                .and_then(FromPrimitive::from_f64),
+        })
+    }
+}
+
+/// PGN 129809, a fast packet message.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AISClassBStaticDataPartA {
+    /// AIS message 24.
+    pub message_id: u8,
+    pub repeat_indicator: u8,
+    pub mmsi: u32,
+    pub name: String,
+}
+
+impl Message<AISClassBStaticDataPartA, &[u8]> for AISClassBStaticDataPartA {
+    fn get_data(data: &[u8]) -> Result<AISClassBStaticDataPartA, NmeaError> {
+        let fields = AISClassBStaticDataPartAFields::get_data(data)?;
+        Ok(AISClassBStaticDataPartA {
+            message_id: message_id(fields.message_id),
+            repeat_indicator: repeat_indicator(fields.repeat_indicator),
+            mmsi: mmsi(fields.user_id),
+            name: parse_at(data, 5, take_fixed_string(20))?,
+        })
+    }
+}
+
+/// PGN 129810, a fast packet message.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AISClassBStaticDataPartB {
+    /// AIS message 24.
+    pub message_id: u8,
+    pub repeat_indicator: u8,
+    pub mmsi: u32,
+    /// ITU-R M.1371 type of ship and cargo.
+    pub ship_type: FieldValue<u8>,
+    pub vendor_id: String,
+    pub callsign: String,
+    pub dimensions: AISDimensions,
+    /// Set by auxiliary craft to the MMSI of the ship that carries them.
+    pub mothership_mmsi: FieldValue<u32>,
+}
+
+impl Message<AISClassBStaticDataPartB, &[u8]> for AISClassBStaticDataPartB {
+    fn get_data(data: &[u8]) -> Result<AISClassBStaticDataPartB, NmeaError> {
+        let fields = AISClassBStaticDataPartBFields::get_data(data)?;
+        let (vendor_id, callsign) =
+            parse_at(data, 6, pair(take_fixed_string(7), take_fixed_string(7)))?;
+        Ok(AISClassBStaticDataPartB {
+            message_id: message_id(fields.message_id),
+            repeat_indicator: repeat_indicator(fields.repeat_indicator),
+            mmsi: mmsi(fields.user_id),
+            ship_type: fields.type_of_ship.map(|ship_type| ship_type as u8),
+            vendor_id,
+            callsign,
+            dimensions: dimensions(
+                fields.length,
+                fields.beam,
+                fields.position_reference_from_starboard,
+                fields.position_reference_from_bow,
+            ),
+            mothership_mmsi: fields.mothership_user_id.map(|mmsi| mmsi as u32),
+        })
+    }
+}
+
+/// PGN 129041, a fast packet message.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AISAidsToNavigationReport {
+    /// AIS message 21.
+    pub message_id: u8,
+    pub repeat_indicator: u8,
+    pub mmsi: u32,
+    pub longitude: FieldValue<Longitude>,
+    pub latitude: FieldValue<Latitude>,
+    /// Better than 10 m when set.
+    pub high_position_accuracy: bool,
+    pub raim: bool,
+    pub time_stamp: AISTimeStamp,
+    pub dimensions: AISDimensions,
+    /// ITU-R M.1371 aid to navigation type, 0 when unspecified.
+    pub aton_type: u8,
+    /// The floating aid is off its charted position.
+    pub off_position: bool,
+    /// No physical aid exists at the reported position.
+    pub virtual_aton: bool,
+    pub assigned_mode: bool,
+    pub position_fixing_device: Option<PositionFixingDevice>,
+    pub aton_status: u8,
+    pub transceiver: Option<AISTransceiver>,
+    pub name: String,
+}
+
+impl Message<AISAidsToNavigationReport, &[u8]> for AISAidsToNavigationReport {
+    fn get_data(data: &[u8]) -> Result<AISAidsToNavigationReport, NmeaError> {
+        let fields = AISAidsToNavigationReportFields::get_data(data)?;
+        Ok(AISAidsToNavigationReport {
+            message_id: message_id(fields.message_id),
+            repeat_indicator: repeat_indicator(fields.repeat_indicator),
+            mmsi: mmsi(fields.user_id),
+            longitude: fields.longitude.map(Longitude::from_degrees),
+            latitude: fields.latitude.map(Latitude::from_degrees),
+            high_position_accuracy: is_set(fields.position_accuracy),
+            raim: is_set(fields.raim),
+            time_stamp: time_stamp(fields.time_stamp),
+            dimensions: dimensions(
+                fields.length_diameter,
+                fields.beam_diameter,
+                fields.position_reference_from_starboard_edge,
+                fields.position_reference_from_true_north_facing_edge,
+            ),
+            aton_type: fields
+                .aton_type
+                .value()
+                .map_or(0x1F, |aton_type| aton_type as u8),
+            off_position: is_set(fields.off_position_indicator),
+            virtual_aton: is_set(fields.virtual_aton_flag),
+            assigned_mode: is_set(fields.assigned_mode_flag),
+            position_fixing_device: fields
+                .position_fixing_device_type
+                .value()
+                .and_then(FromPrimitive::from_f64),
+            aton_status: fields
+                .aton_status
+                .value()
+                .map_or(0xFF, |status| status as u8),
+            transceiver: fields
+                .ais_transceiver_information
+                .value()
+                .and_then(FromPrimitive::from_f64),
+            name: parse_at(data, 26, take_lau_string)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_class_a_position_report() {
+        // MMSI 244123456 at 52.3702157 N 4.8952 E, high accuracy, no RAIM,
+        // second 42, communication state 0x12345 received on channel B
+        let payload = [
+            0x01, 0x40, 0x07, 0x8D, 0x0E, 0xC0, 0xF2, 0xEA, 0x02, 0x8D, 0x0F, 0x37, 0x1F, 0xA9,
+            0x10, 0x27, 0x01, 0x01, 0x45, 0x23, 0x09, 0x54, 0x3D, 0xC0, 0xFE, 0xD8, 0xFF, 0xFF,
+        ];
+
+        let report = AISClassAPositionReport::get_data(&payload).unwrap();
+        assert_eq!(1, report.message_id);
+        assert_eq!(0, report.repeat_indicator);
+        assert_eq!(244123456, report.mmsi);
+        assert_eq!(52.3702157, report.latitude.value().unwrap().get_degrees());
+        assert_eq!(4.8952, report.longitude.value().unwrap().get_degrees());
+        assert!(report.high_position_accuracy);
+        assert!(!report.raim);
+        assert_eq!(AISTimeStamp::Second(42), report.time_stamp);
+        assert_eq!(1.0, report.cog.value().unwrap().get_radians());
+        assert_eq!(2.57, report.sog.value().unwrap().get_meters_per_second());
+        assert_eq!(0x12345, report.communication_state);
+        assert_eq!(Some(AISTransceiver::ChannelBReception), report.transceiver);
+        assert_eq!(FieldValue::Value(Rad::new(15700)), report.heading);
+        assert_eq!(
+            -0.01,
+            report
+                .rate_of_turn
+                .value()
+                .unwrap()
+                .get_radians_per_second()
+        );
+        assert_eq!(
+            Some(NavigationalStatus::UnderWaySailing),
+            report.navigational_status
+        );
+        assert_eq!(Some(SpecialManeuver::NotEngaged), report.special_maneuver);
+        assert_eq!(FieldValue::NotAvailable, report.sequence_id);
+    }
+
+    #[test]
+    fn parses_class_b_position_report() {
+        // Carrier sense unit with a display, handles message 22
+        let payload = [
+            0x12, 0x40, 0x07, 0x8D, 0x0E, 0xC0, 0xF2, 0xEA, 0x02, 0x8D, 0x0F, 0x37, 0x1F, 0xA9,
+            0x10, 0x27, 0x01, 0x01, 0x45, 0x23, 0x09, 0x54, 0x3D, 0xFF, 0x4F, 0xFE, 0xFF,
+        ];
+
+        let report = AISClassBPositionReport::get_data(&payload).unwrap();
+        assert_eq!(18, report.message_id);
+        assert_eq!(244123456, report.mmsi);
+        assert_eq!(52.3702157, report.latitude.value().unwrap().get_degrees());
+        assert_eq!(Some(AISTransceiver::ChannelBReception), report.transceiver);
+        assert!(report.unit_flags.carrier_sense());
+        assert!(report.unit_flags.integrated_display());
+        assert!(!report.unit_flags.dsc());
+        assert!(!report.unit_flags.whole_marine_band());
+        assert!(report.unit_flags.can_handle_message_22());
+        assert!(!report.unit_flags.assigned_mode());
+        assert!(!report.unit_flags.itdma_communication_state());
+    }
+
+    #[test]
+    fn parses_class_a_static_data() {
+        // EMMA MAERSK for Rotterdam, 2024-03-01 at 12:30, Edition 3, GPS,
+        // DTE available
+        let payload = [
+            0x05, 0x40, 0x07, 0x8D, 0x0E, 0x29, 0x78, 0x8A, 0x00, 0x50, 0x44, 0x31, 0x32, 0x33,
+            0x34, 0x40, 0x45, 0x4D, 0x4D, 0x41, 0x20, 0x4D, 0x41, 0x45, 0x52, 0x53, 0x4B, 0x40,
+            0x40, 0x40, 0x40, 0x40, 0x40, 0x40, 0x40, 0x40, 0x46, 0x82, 0x0F, 0x34, 0x02, 0x18,
+            0x01, 0xAC, 0x0D, 0x47, 0x4D, 0x80, 0x74, 0xD2, 0x1A, 0xE2, 0x04, 0x52, 0x4F, 0x54,
+            0x54, 0x45, 0x52, 0x44, 0x41, 0x4D, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20,
+            0x20, 0x20, 0x20, 0x85, 0xE0,
+        ];
+
+        let static_data = AISClassAStaticData::get_data(&payload).unwrap();
+        assert_eq!(5, static_data.message_id);
+        assert_eq!(244123456, static_data.mmsi);
+        assert_eq!(FieldValue::Value(9074729), static_data.imo_number);
+        assert_eq!("PD1234", static_data.callsign);
+        assert_eq!("EMMA MAERSK", static_data.name);
+        assert_eq!("ROTTERDAM", static_data.destination);
+        assert_eq!(FieldValue::Value(70), static_data.ship_type);
+        assert_eq!(
+            397.0,
+            static_data.dimensions.length.value().unwrap().get_meters()
+        );
+        assert_eq!(
+            56.4,
+            static_data.dimensions.beam.value().unwrap().get_meters()
+        );
+        assert_eq!(12.5, static_data.draught.value().unwrap().get_meters());
+        let eta = static_data.get_eta().unwrap();
+        assert_eq!((2024, 3, 1), eta.date.get_year_month_day());
+        assert_eq!((12, 30, 0), eta.time.get_hour_minute_second());
+        assert_eq!(Some(AISVersion::Edition3), static_data.ais_version);
+        assert_eq!(
+            Some(PositionFixingDevice::GPS),
+            static_data.position_fixing_device
+        );
+        assert!(static_data.dte_available);
+        assert_eq!(
+            Some(AISTransceiver::ChannelAReception),
+            static_data.transceiver
+        );
+    }
+
+    #[test]
+    fn parses_class_b_static_data() {
+        let part_a = [
+            0x18, 0xF1, 0x20, 0x95, 0x0E, 0x53, 0x45, 0x41, 0x20, 0x44, 0x4F, 0x47, 0xFF, 0xFF,
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+        ];
+        let part_a = AISClassBStaticDataPartA::get_data(&part_a).unwrap();
+        assert_eq!(24, part_a.message_id);
+        assert_eq!(244654321, part_a.mmsi);
+        assert_eq!("SEA DOG", part_a.name);
+
+        let part_b = [
+            0x18, 0xF1, 0x20, 0x95, 0x0E, 0x24, 0x54, 0x52, 0x4D, 0x40, 0x40, 0x40, 0x40, 0x50,
+            0x41, 0x32, 0x33, 0x34, 0x35, 0x20, 0x78, 0x00, 0x26, 0x00, 0x13, 0x00, 0xFF, 0xFF,
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+        ];
+        let part_b = AISClassBStaticDataPartB::get_data(&part_b).unwrap();
+        assert_eq!(244654321, part_b.mmsi);
+        assert_eq!(FieldValue::Value(36), part_b.ship_type);
+        assert_eq!("TRM", part_b.vendor_id);
+        assert_eq!("PA2345", part_b.callsign);
+        assert_eq!(12.0, part_b.dimensions.length.value().unwrap().get_meters());
+        assert_eq!(3.8, part_b.dimensions.beam.value().unwrap().get_meters());
+        assert_eq!(
+            FieldValue::NotAvailable,
+            part_b.dimensions.position_reference_from_bow
+        );
+        assert_eq!(FieldValue::NotAvailable, part_b.mothership_mmsi);
+    }
+
+    #[test]
+    fn parses_aids_to_navigation_report() {
+        // Virtual north cardinal mark at a surveyed position
+        let payload = [
+            0x15, 0x31, 0x86, 0x27, 0x3B, 0xC0, 0xF2, 0xEA, 0x02, 0x8D, 0x0F, 0x37, 0x1F, 0xF1,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x54, 0xEF, 0x00, 0xE2, 0x0B, 0x01,
+            0x57, 0x52, 0x45, 0x43, 0x4B, 0x20, 0x4E, 0x40, 0x40,
+        ];
+
+        let report = AISAidsToNavigationReport::get_data(&payload).unwrap();
+        assert_eq!(21, report.message_id);
+        assert_eq!(992446001, report.mmsi);
+        assert_eq!(52.3702157, report.latitude.value().unwrap().get_degrees());
+        assert_eq!(4.8952, report.longitude.value().unwrap().get_degrees());
+        assert_eq!(AISTimeStamp::NotAvailable, report.time_stamp);
+        assert_eq!(20, report.aton_type);
+        assert!(report.virtual_aton);
+        assert!(!report.off_position);
+        assert!(!report.assigned_mode);
+        assert_eq!(
+            Some(PositionFixingDevice::Surveyed),
+            report.position_fixing_device
+        );
+        assert_eq!(
+            Some(AISTransceiver::ChannelATransmission),
+            report.transceiver
+        );
+        assert_eq!("WRECK N", report.name);
+    }
+}
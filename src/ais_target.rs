@@ -0,0 +1,502 @@
+//! Keeps track of the AIS targets around us and how close they will come.
+//!
+//! Nothing here reads the clock: every update takes `now`, a monotonic
+//! timestamp supplied by the caller, so encounters can be replayed in tests.
+
+use core::time::Duration;
+use std::collections::HashMap;
+
+use crate::{
+    ais::{
+        AISClassAPositionReport, AISClassAStaticData, AISClassBPositionReport,
+        AISClassBStaticDataPartA, AISClassBStaticDataPartB, AISDimensions, NavigationalStatus,
+    },
+    course_over_ground::CourseOverGround,
+    field_value::FieldValue,
+    position_rapid_update::PositionRapidUpdate,
+    rad::Rad,
+    units::{Distance, Latitude, Longitude, Speed},
+    vessel_heading::DirectionReference,
+};
+
+/// Mean earth radius used for the local flat earth projection.
+const EARTH_RADIUS_METERS: f64 = 6_371_000.0;
+
+/// How long a target is kept after its last report.
+pub const DEFAULT_TARGET_TIMEOUT: Duration = Duration::from_secs(6 * 60);
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum AISClass {
+    A,
+    B,
+}
+
+/// Everything known about one vessel, merged from its position and static
+/// reports.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AISTarget {
+    pub mmsi: u32,
+    pub class: Option<AISClass>,
+    pub name: Option<String>,
+    pub callsign: Option<String>,
+    pub destination: Option<String>,
+    pub ship_type: FieldValue<u8>,
+    pub dimensions: Option<AISDimensions>,
+    pub latitude: FieldValue<Latitude>,
+    pub longitude: FieldValue<Longitude>,
+    pub cog: FieldValue<Rad>,
+    pub sog: FieldValue<Speed>,
+    pub heading: FieldValue<Rad>,
+    pub navigational_status: Option<NavigationalStatus>,
+    /// When the latest position report arrived.
+    pub position_time: Option<Duration>,
+    /// When any report for this target last arrived.
+    pub last_seen: Duration,
+}
+
+impl AISTarget {
+    fn new(mmsi: u32, now: Duration) -> Self {
+        AISTarget {
+            mmsi,
+            class: None,
+            name: None,
+            callsign: None,
+            destination: None,
+            ship_type: FieldValue::NotAvailable,
+            dimensions: None,
+            latitude: FieldValue::NotAvailable,
+            longitude: FieldValue::NotAvailable,
+            cog: FieldValue::NotAvailable,
+            sog: FieldValue::NotAvailable,
+            heading: FieldValue::NotAvailable,
+            navigational_status: None,
+            position_time: None,
+            last_seen: now,
+        }
+    }
+
+    fn motion(&self) -> Option<Motion> {
+        Some(Motion {
+            latitude: self.latitude.value()?.get_degrees(),
+            longitude: self.longitude.value()?.get_degrees(),
+            cog: self
+                .cog
+                .value()
+                .map_or(0.0, |cog| f64::from(cog.get_radians())),
+            sog: self
+                .sog
+                .value()
+                .map_or(0.0, |sog| f64::from(sog.get_meters_per_second())),
+            time: self.position_time?,
+        })
+    }
+}
+
+/// Closest point of approach between our own ship and a target.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ClosestApproach {
+    /// Distance between the two ships at the closest point.
+    pub distance: Distance,
+    /// Time until the closest point is reached. Zero when the ships are
+    /// already moving apart, in which case `distance` is the current range.
+    pub time: Duration,
+    /// Current distance between the two ships.
+    pub range: Distance,
+}
+
+/// A position, course and speed at a point in time.
+#[derive(Debug, Clone, Copy)]
+struct Motion {
+    latitude: f64,
+    longitude: f64,
+    /// Radians, true.
+    cog: f64,
+    /// Meters per second.
+    sog: f64,
+    time: Duration,
+}
+
+impl Motion {
+    /// East and north velocity in meters per second.
+    fn velocity(&self) -> (f64, f64) {
+        (self.sog * self.cog.sin(), self.sog * self.cog.cos())
+    }
+
+    /// East and north offset in meters from `origin`, with both positions
+    /// dead reckoned to `now`.
+    fn offset_from(&self, origin: &Motion, now: Duration) -> (f64, f64) {
+        let (east, north) = self.projected(origin.latitude, origin.longitude, now);
+        let (origin_east, origin_north) = origin.projected(origin.latitude, origin.longitude, now);
+        (east - origin_east, north - origin_north)
+    }
+
+    fn projected(&self, latitude: f64, longitude: f64, now: Duration) -> (f64, f64) {
+        let elapsed = now.as_secs_f64() - self.time.as_secs_f64();
+        let (east_velocity, north_velocity) = self.velocity();
+        // The short way round, across the antimeridian if that is closer
+        let longitude_delta = (self.longitude - longitude + 540.0).rem_euclid(360.0) - 180.0;
+        let east = longitude_delta.to_radians() * latitude.to_radians().cos() * EARTH_RADIUS_METERS;
+        let north = (self.latitude - latitude).to_radians() * EARTH_RADIUS_METERS;
+        (
+            east + east_velocity * elapsed,
+            north + north_velocity * elapsed,
+        )
+    }
+}
+
+/// AIS targets keyed by MMSI.
+pub struct AISTargetTable {
+    targets: HashMap<u32, AISTarget>,
+    own_ship: Option<Motion>,
+    /// Our latest true course in radians and speed in meters per second.
+    own_course: (f64, f64),
+    timeout: Duration,
+}
+
+impl AISTargetTable {
+    pub fn new() -> Self {
+        Self::with_timeout(DEFAULT_TARGET_TIMEOUT)
+    }
+
+    /// A table that drops targets which have not reported for `timeout`.
+    pub fn with_timeout(timeout: Duration) -> Self {
+        AISTargetTable {
+            targets: HashMap::new(),
+            own_ship: None,
+            own_course: (0.0, 0.0),
+            timeout,
+        }
+    }
+
+    fn target(&mut self, mmsi: u32, now: Duration) -> &mut AISTarget {
+        let target = self
+            .targets
+            .entry(mmsi)
+            .or_insert_with(|| AISTarget::new(mmsi, now));
+        target.last_seen = now;
+        target
+    }
+
+    pub fn update_class_a_position(&mut self, report: &AISClassAPositionReport, now: Duration) {
+        let target = self.target(report.mmsi, now);
+        target.class = Some(AISClass::A);
+        target.latitude = report.latitude;
+        target.longitude = report.longitude;
+        target.cog = report.cog;
+        target.sog = report.sog;
+        target.heading = report.heading;
+        target.navigational_status = report.navigational_status;
+        target.position_time = Some(now);
+    }
+
+    pub fn update_class_b_position(&mut self, report: &AISClassBPositionReport, now: Duration) {
+        let target = self.target(report.mmsi, now);
+        target.class = Some(AISClass::B);
+        target.latitude = report.latitude;
+        target.longitude = report.longitude;
+        target.cog = report.cog;
+        target.sog = report.sog;
+        target.heading = report.heading;
+        target.position_time = Some(now);
+    }
+
+    pub fn update_class_a_static_data(&mut self, static_data: &AISClassAStaticData, now: Duration) {
+        let target = self.target(static_data.mmsi, now);
+        target.class = Some(AISClass::A);
+        target.name = Some(static_data.name.clone());
+        target.callsign = Some(static_data.callsign.clone());
+        target.destination = Some(static_data.destination.clone());
+        target.ship_type = static_data.ship_type;
+        target.dimensions = Some(static_data.dimensions);
+    }
+
+    pub fn update_class_b_static_data_part_a(
+        &mut self,
+        static_data: &AISClassBStaticDataPartA,
+        now: Duration,
+    ) {
+        let target = self.target(static_data.mmsi, now);
+        target.class = Some(AISClass::B);
+        target.name = Some(static_data.name.clone());
+    }
+
+    pub fn update_class_b_static_data_part_b(
+        &mut self,
+        static_data: &AISClassBStaticDataPartB,
+        now: Duration,
+    ) {
+        let target = self.target(static_data.mmsi, now);
+        target.class = Some(AISClass::B);
+        target.callsign = Some(static_data.callsign.clone());
+        target.ship_type = static_data.ship_type;
+        target.dimensions = Some(static_data.dimensions);
+    }
+
+    /// Records our own position, from PGN 129025.
+    pub fn update_own_position(&mut self, position: &PositionRapidUpdate, now: Duration) {
+        let (Some(latitude), Some(longitude)) =
+            (position.latitude.value(), position.longitude.value())
+        else {
+            return;
+        };
+        let (cog, sog) = self.own_course;
+        self.own_ship = Some(Motion {
+            latitude: latitude.get_degrees(),
+            longitude: longitude.get_degrees(),
+            cog,
+            sog,
+            time: now,
+        });
+    }
+
+    /// Records our own course and speed, from PGN 129026. Magnetic courses
+    /// are ignored since AIS courses are always true.
+    pub fn update_own_course(&mut self, course: &CourseOverGround, now: Duration) {
+        if course.cog_reference != Some(DirectionReference::True) {
+            return;
+        }
+        let (Some(cog), Some(sog)) = (course.cog.value(), course.sog.value()) else {
+            return;
+        };
+        self.own_course = (
+            f64::from(cog.get_radians()),
+            f64::from(sog.get_meters_per_second()),
+        );
+        if let Some(own_ship) = self.own_ship.as_mut() {
+            // Carry the position forward so the new course applies from now.
+            let elapsed = now.as_secs_f64() - own_ship.time.as_secs_f64();
+            let (east_velocity, north_velocity) = own_ship.velocity();
+            own_ship.latitude += (north_velocity * elapsed / EARTH_RADIUS_METERS).to_degrees();
+            own_ship.longitude += (east_velocity * elapsed
+                / (EARTH_RADIUS_METERS * own_ship.latitude.to_radians().cos()))
+            .to_degrees();
+            own_ship.longitude = (own_ship.longitude + 540.0).rem_euclid(360.0) - 180.0;
+            own_ship.cog = self.own_course.0;
+            own_ship.sog = self.own_course.1;
+            own_ship.time = now;
+        }
+    }
+
+    /// Drops targets that have not reported within the timeout.
+    pub fn expire(&mut self, now: Duration) {
+        let timeout = self.timeout;
+        self.targets
+            .retain(|_, target| now.saturating_sub(target.last_seen) <= timeout);
+    }
+
+    pub fn get(&self, mmsi: u32) -> Option<&AISTarget> {
+        self.targets.get(&mmsi)
+    }
+
+    pub fn targets(&self) -> impl Iterator<Item = &AISTarget> {
+        self.targets.values()
+    }
+
+    /// CPA and TCPA of a target, with both ships dead reckoned to `now`.
+    /// `None` until both our own position and the target's are known.
+    pub fn closest_approach(&self, mmsi: u32, now: Duration) -> Option<ClosestApproach> {
+        let own_ship = self.own_ship?;
+        let target = self.targets.get(&mmsi)?.motion()?;
+
+        let (east, north) = target.offset_from(&own_ship, now);
+        let (target_east_velocity, target_north_velocity) = target.velocity();
+        let (own_east_velocity, own_north_velocity) = own_ship.velocity();
+        let east_velocity = target_east_velocity - own_east_velocity;
+        let north_velocity = target_north_velocity - own_north_velocity;
+
+        let range = east.hypot(north);
+        let relative_speed_squared =
+            east_velocity * east_velocity + north_velocity * north_velocity;
+        let time = if relative_speed_squared > f64::EPSILON {
+            (-(east * east_velocity + north * north_velocity) / relative_speed_squared).max(0.0)
+        } else {
+            0.0
+        };
+        let distance = (east + east_velocity * time).hypot(north + north_velocity * time);
+
+        Some(ClosestApproach {
+            distance: Distance::from_meters(distance),
+            time: Duration::from_secs_f64(time),
+            range: Distance::from_meters(range),
+        })
+    }
+
+    /// Targets that will come within `distance` in the next `time`, closest
+    /// first.
+    pub fn dangerous_targets(
+        &self,
+        distance: Distance,
+        time: Duration,
+        now: Duration,
+    ) -> Vec<(&AISTarget, ClosestApproach)> {
+        let mut dangerous: Vec<(&AISTarget, ClosestApproach)> = self
+            .targets
+            .values()
+            .filter_map(|target| Some((target, self.closest_approach(target.mmsi, now)?)))
+            .filter(|(_, approach)| {
+                approach.distance.get_meters() <= distance.get_meters() && approach.time <= time
+            })
+            .collect();
+        dangerous
+            .sort_by(|(_, a), (_, b)| a.distance.get_meters().total_cmp(&b.distance.get_meters()));
+        dangerous
+    }
+}
+
+impl Default for AISTargetTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ais::{AISTimeStamp, ClassBUnitFlags};
+
+    const MMSI: u32 = 244123456;
+
+    fn position_report(
+        latitude: f64,
+        longitude: f64,
+        cog: f32,
+        sog: f32,
+    ) -> AISClassBPositionReport {
+        AISClassBPositionReport {
+            message_id: 18,
+            repeat_indicator: 0,
+            mmsi: MMSI,
+            longitude: FieldValue::Value(Longitude::from_degrees(longitude)),
+            latitude: FieldValue::Value(Latitude::from_degrees(latitude)),
+            high_position_accuracy: true,
+            raim: false,
+            time_stamp: AISTimeStamp::NotAvailable,
+            cog: FieldValue::Value(Rad::from_radians(cog)),
+            sog: FieldValue::Value(Speed::from_meters_per_second(sog)),
+            communication_state: 0,
+            transceiver: None,
+            heading: FieldValue::NotAvailable,
+            unit_flags: ClassBUnitFlags(0),
+        }
+    }
+
+    fn own_ship(table: &mut AISTargetTable, cog: f32, sog: f32, now: Duration) {
+        table.update_own_position(
+            &PositionRapidUpdate {
+                latitude: FieldValue::Value(Latitude::from_degrees(52.0)),
+                longitude: FieldValue::Value(Longitude::from_degrees(4.0)),
+            },
+            now,
+        );
+        let mut course = CourseOverGround::new();
+        course.cog = FieldValue::Value(Rad::from_radians(cog));
+        course.sog = FieldValue::Value(Speed::from_meters_per_second(sog));
+        table.update_own_course(&course, now);
+    }
+
+    #[test]
+    fn computes_head_on_encounter() {
+        let mut table = AISTargetTable::new();
+        let now = Duration::from_secs(100);
+        // Heading north at 5 m/s, target one nautical mile ahead heading south
+        own_ship(&mut table, 0.0, 5.0, now);
+        let ahead = 52.0 + (1852.0 / EARTH_RADIUS_METERS).to_degrees();
+        table.update_class_b_position(
+            &position_report(ahead, 4.0, core::f32::consts::PI, 5.0),
+            now,
+        );
+
+        let approach = table.closest_approach(MMSI, now).unwrap();
+        assert!((approach.range.get_meters() - 1852.0).abs() < 0.01);
+        assert!(approach.distance.get_meters() < 0.5);
+        assert!((approach.time.as_secs_f64() - 185.2).abs() < 0.1);
+
+        // A minute later both have closed by 600 m
+        let later = table
+            .closest_approach(MMSI, now + Duration::from_secs(60))
+            .unwrap();
+        assert!((later.range.get_meters() - 1252.0).abs() < 0.5);
+        assert!((later.time.as_secs_f64() - 125.2).abs() < 0.1);
+    }
+
+    #[test]
+    fn reports_current_range_once_passed() {
+        let mut table = AISTargetTable::new();
+        let now = Duration::from_secs(0);
+        own_ship(&mut table, 0.0, 5.0, now);
+        // Target 1 km east, moving away east
+        let east =
+            4.0 + (1000.0 / (EARTH_RADIUS_METERS * 52.0_f64.to_radians().cos())).to_degrees();
+        table.update_class_b_position(
+            &position_report(52.0, east, core::f32::consts::FRAC_PI_2, 3.0),
+            now,
+        );
+
+        let approach = table.closest_approach(MMSI, now).unwrap();
+        assert_eq!(Duration::ZERO, approach.time);
+        assert!((approach.distance.get_meters() - 1000.0).abs() < 0.5);
+        assert!(table
+            .dangerous_targets(Distance::from_meters(500.0), Duration::from_secs(600), now)
+            .is_empty());
+    }
+
+    #[test]
+    fn computes_approach_across_antimeridian() {
+        let mut table = AISTargetTable::new();
+        let now = Duration::from_secs(0);
+        // On the equator heading east at 5 m/s, target 0.02 degrees further
+        // east on the other side of 180 degrees, heading west
+        table.update_own_position(
+            &PositionRapidUpdate {
+                latitude: FieldValue::Value(Latitude::from_degrees(0.0)),
+                longitude: FieldValue::Value(Longitude::from_degrees(179.99)),
+            },
+            now,
+        );
+        let mut course = CourseOverGround::new();
+        course.cog = FieldValue::Value(Rad::from_radians(core::f32::consts::FRAC_PI_2));
+        course.sog = FieldValue::Value(Speed::from_meters_per_second(5.0));
+        table.update_own_course(&course, now);
+        table.update_class_b_position(
+            &position_report(0.0, -179.99, 3.0 * core::f32::consts::FRAC_PI_2, 5.0),
+            now,
+        );
+
+        let expected = 0.02_f64.to_radians() * EARTH_RADIUS_METERS;
+        let approach = table.closest_approach(MMSI, now).unwrap();
+        assert!((approach.range.get_meters() - expected).abs() < 1.0);
+        assert!(approach.distance.get_meters() < 1.0);
+        assert!((approach.time.as_secs_f64() - expected / 10.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn merges_static_data_and_expires_targets() {
+        let mut table = AISTargetTable::with_timeout(Duration::from_secs(60));
+        table.update_class_b_static_data_part_a(
+            &AISClassBStaticDataPartA {
+                message_id: 24,
+                repeat_indicator: 0,
+                mmsi: MMSI,
+                name: "SEA DOG".to_string(),
+            },
+            Duration::from_secs(0),
+        );
+        assert!(table
+            .closest_approach(MMSI, Duration::from_secs(0))
+            .is_none());
+        table.update_class_b_position(
+            &position_report(52.0, 4.0, 0.0, 0.0),
+            Duration::from_secs(30),
+        );
+
+        let target = table.get(MMSI).unwrap();
+        assert_eq!(Some(AISClass::B), target.class);
+        assert_eq!(Some("SEA DOG".to_string()), target.name);
+        assert_eq!(Some(Duration::from_secs(30)), target.position_time);
+
+        table.expire(Duration::from_secs(90));
+        assert_eq!(1, table.targets().count());
+        table.expire(Duration::from_secs(91));
+        assert_eq!(0, table.targets().count());
+    }
+}
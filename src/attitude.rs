@@ -0,0 +1,41 @@
+use crate::{
+    field_value::FieldValue, nmea_frame::AttitudeFrame, pgn_fields::AttitudeFields, rad::Rad,
+    Message, NmeaError,
+};
+
+/// PGN 127257
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Attitude {
+    pub sid: FieldValue<u8>,
+    pub yaw: FieldValue<Rad>,
+    /// Positive with the bow up.
+    pub pitch: FieldValue<Rad>,
+    /// Positive with the port side up.
+    pub roll: FieldValue<Rad>,
+}
+
+impl Message<Attitude, AttitudeFrame> for Attitude {
+    fn get_data(frame: AttitudeFrame) -> Result<Attitude, NmeaError> {
+        let fields = AttitudeFields::get_data(&frame.data)?;
+        Ok(Attitude {
+            sid: fields.sid.map(|sid| sid as u8),
+            yaw: fields.yaw.map(|yaw| Rad::from_radians(yaw as f32)),
+            pitch: fields.pitch.map(|pitch| Rad::from_radians(pitch as f32)),
+            roll: fields.roll.map(|roll| Rad::from_radians(roll as f32)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_attitude() {
+        let data = [0, 0xFF, 0x7F, 0xB9, 0x06, 0x47, 0xF9, 0xFF];
+        let attitude = Attitude::get_data(AttitudeFrame { data }).unwrap();
+        assert_eq!(FieldValue::NotAvailable, attitude.yaw);
+        assert_eq!(0.1721, attitude.pitch.value().unwrap().get_radians());
+        assert_eq!(-0.1721, attitude.roll.value().unwrap().get_radians());
+    }
+}
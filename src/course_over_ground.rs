@@ -1,58 +1,45 @@
 use crate::{
-    nmea_frame::COGSOGRapidUpdateFrame,
-    parse::{take_byte, take_nibble, take_two_bits, take_u16, BitInput},
-    rad::Rad,
-    vessel_heading::DirectionReference,
-    Message, NmeaError,
+    field_value::FieldValue, nmea_frame::COGSOGRapidUpdateFrame,
+    pgn_fields::COGSOGRapidUpdateFields, rad::Rad, units::Speed,
+    vessel_heading::DirectionReference, Message, NmeaError,
 };
-use nom::IResult;
-use num_traits::FromPrimitive;
 
 #[derive(Debug)]
 pub struct CourseOverGround {
-    pub sid: u8,
+    pub sid: FieldValue<u8>,
     pub cog_reference: Option<DirectionReference>,
-    pub cog: Rad,
-    pub sog: u16,
+    pub cog: FieldValue<Rad>,
+    pub sog: FieldValue<Speed>,
 }
 
 impl CourseOverGround {
     pub fn new() -> Self {
         CourseOverGround {
-            sid: 0,
+            sid: FieldValue::Value(0),
             cog_reference: Some(DirectionReference::True),
-            cog: Rad::new(0),
-            sog: 0,
+            cog: FieldValue::Value(Rad::new(0)),
+            sog: FieldValue::Value(Speed::from_meters_per_second(0.0)),
         }
     }
 }
 
-fn parse_course_over_ground(i: BitInput) -> IResult<BitInput, CourseOverGround> {
-    let (i, sid) = take_byte(i)?;
-    let (i, cog_reference) = take_two_bits(i)?;
-    let (i, _) = take_nibble(i)?;
-    let (i, _) = take_two_bits(i)?;
-    let (i, cog) = take_u16(i)?;
-    let (i, sog) = take_u16(i)?;
-
-    let system_time = CourseOverGround {
-        sid,
-        cog_reference: FromPrimitive::from_u8(cog_reference),
-        cog: Rad::new(cog),
-        sog,
-    };
-    Ok((i, system_time))
+impl Default for CourseOverGround {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Message<CourseOverGround, COGSOGRapidUpdateFrame> for CourseOverGround {
     fn get_data(frame: COGSOGRapidUpdateFrame) -> Result<CourseOverGround, NmeaError> {
-        let data = frame.data;
-        let parse_result: IResult<&[u8], CourseOverGround> =
-            nom::bits::bits(parse_course_over_ground)(&data);
-        match parse_result {
-            Ok((_, system_time)) => Ok(system_time),
-            Err(_e) => Err(NmeaError::ParseError),
-        }
+        let fields = COGSOGRapidUpdateFields::get_data(&frame.data)?;
+        Ok(CourseOverGround {
+            sid: fields.sid.map(|sid| sid as u8),
+            cog_reference: DirectionReference::from_field(fields.cog_reference),
+            cog: fields.cog.map(|cog| Rad::from_radians(cog as f32)),
+            sog: fields
+                .sog
+                .map(|sog| Speed::from_meters_per_second(sog as f32)),
+        })
     }
 }
 
@@ -67,8 +54,7 @@ mod tests {
         let data = parsed_data.unwrap();
         // TODO: Why is it error?
         assert_eq!(DirectionReference::Error, data.cog_reference.unwrap());
-        assert_eq!(6.5534997, data.cog.get_radians());
-        // TODO: This should be a float (unit is m/s)
-        assert_eq!(65535, data.sog);
+        assert_eq!(FieldValue::NotAvailable, data.cog);
+        assert_eq!(FieldValue::NotAvailable, data.sog);
     }
 }
@@ -0,0 +1,81 @@
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use crate::{
+    field_value::FieldValue, nmea_frame::CrossTrackErrorFrame, pgn_fields::CrossTrackErrorFields,
+    units::Distance, Encode, Message, NmeaError,
+};
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum XTEMode {
+    Autonomous = 0,
+    Differential = 1,
+    Estimated = 2,
+    Simulator = 3,
+    Manual = 4,
+}
+
+/// PGN 129283
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct CrossTrackError {
+    pub sid: FieldValue<u8>,
+    pub mode: Option<XTEMode>,
+    /// Set once the route has been completed or navigation stopped.
+    pub navigation_terminated: FieldValue<bool>,
+    /// Positive when the vessel is right of the track and should steer left.
+    pub xte: FieldValue<Distance>,
+}
+
+impl Message<CrossTrackError, CrossTrackErrorFrame> for CrossTrackError {
+    fn get_data(frame: CrossTrackErrorFrame) -> Result<CrossTrackError, NmeaError> {
+        let fields = CrossTrackErrorFields::get_data(&frame.data)?;
+        Ok(CrossTrackError {
+            sid: fields.sid.map(|sid| sid as u8),
+            mode: fields.xte_mode.value().and_then(FromPrimitive::from_f64),
+            navigation_terminated: fields.navigation_terminated.to_status(),
+            xte: fields.xte.map(Distance::from_meters),
+        })
+    }
+}
+
+impl Encode<CrossTrackErrorFrame> for CrossTrackError {
+    fn encode(&self) -> CrossTrackErrorFrame {
+        CrossTrackErrorFields {
+            sid: self.sid.map(f64::from),
+            xte_mode: self.mode.map_or(FieldValue::NotAvailable, |mode| {
+                FieldValue::Value(f64::from(mode as u8))
+            }),
+            navigation_terminated: FieldValue::Value(f64::from(
+                self.navigation_terminated.to_two_bits(),
+            )),
+            xte: self.xte.map(|xte| xte.get_meters()),
+        }
+        .to_frame()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cross_track_error() {
+        // 12.34 m, autonomous, still navigating
+        let data = [0x05, 0x30, 0xD2, 0x04, 0x00, 0x00, 0xFF, 0xFF];
+        let xte = CrossTrackError::get_data(CrossTrackErrorFrame { data }).unwrap();
+        assert_eq!(FieldValue::Value(5), xte.sid);
+        assert_eq!(Some(XTEMode::Autonomous), xte.mode);
+        assert_eq!(FieldValue::Value(false), xte.navigation_terminated);
+        assert_eq!(12.34, xte.xte.value().unwrap().get_meters());
+        assert_eq!(data, xte.encode().data);
+    }
+
+    #[test]
+    fn parses_negative_cross_track_error() {
+        let data = [0xFF, 0x72, 0x9C, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+        let xte = CrossTrackError::get_data(CrossTrackErrorFrame { data }).unwrap();
+        assert_eq!(Some(XTEMode::Estimated), xte.mode);
+        assert_eq!(FieldValue::Value(true), xte.navigation_terminated);
+        assert_eq!(-1.0, xte.xte.value().unwrap().get_meters());
+    }
+}
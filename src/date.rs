@@ -1,2 +1,214 @@
-#[derive(Debug)]
-pub struct Date {}
+/// A date as sent on the bus, counted in days since 1970-01-01.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Date {
+    days: u16,
+}
+
+impl Date {
+    pub fn new(days: u16) -> Self {
+        Date { days }
+    }
+
+    pub fn get_days_since_epoch(&self) -> u16 {
+        self.days
+    }
+
+    /// Returns the (year, month, day) of the proleptic Gregorian calendar.
+    pub fn get_year_month_day(&self) -> (i32, u8, u8) {
+        // Howard Hinnant's civil_from_days, shifted to start years in March
+        let z = i32::from(self.days) + 719468;
+        let era = z / 146097;
+        let day_of_era = z - era * 146097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + i32::from(month <= 2);
+        (year, month as u8, day as u8)
+    }
+}
+
+/// Time of day since midnight UTC with a resolution of 0.1 ms.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Time {
+    time: u32,
+}
+
+impl Time {
+    pub const TICKS_PER_SECOND: u32 = 10000;
+
+    pub fn new(time: u32) -> Time {
+        Time { time }
+    }
+
+    /// Rounds to the nearest tick, as decoded from a field layout.
+    pub fn from_seconds(seconds: f64) -> Time {
+        Time {
+            time: (seconds * f64::from(Self::TICKS_PER_SECOND)).round() as u32,
+        }
+    }
+
+    /// Raw value in units of 0.1 ms.
+    pub fn get_ticks(&self) -> u32 {
+        self.time
+    }
+
+    /// Whole seconds since midnight.
+    pub fn get_seconds(&self) -> u32 {
+        self.time / Self::TICKS_PER_SECOND
+    }
+
+    pub fn get_seconds_f64(&self) -> f64 {
+        f64::from(self.time) / f64::from(Self::TICKS_PER_SECOND)
+    }
+
+    /// Sub-second part of the time in nanoseconds.
+    pub fn get_nanoseconds(&self) -> u32 {
+        (self.time % Self::TICKS_PER_SECOND) * 100_000
+    }
+
+    pub fn get_hour_minute_second(&self) -> (u8, u8, u8) {
+        let seconds = self.get_seconds();
+        (
+            (seconds / 3600) as u8,
+            (seconds / 60 % 60) as u8,
+            (seconds % 60) as u8,
+        )
+    }
+
+    /// A time of day can not be 24:00 or later.
+    pub fn is_valid(&self) -> bool {
+        self.get_seconds() < 86400
+    }
+}
+
+/// A UTC date and time of day, as sent by System Time or as the ETA of
+/// Navigation Data.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DateTime {
+    pub date: Date,
+    pub time: Time,
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_conversions {
+    use super::*;
+    use crate::NmeaError;
+    use chrono::{Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+
+    impl From<Date> for NaiveDate {
+        fn from(date: Date) -> Self {
+            NaiveDate::from_ymd_opt(1970, 1, 1).unwrap()
+                + Duration::days(i64::from(date.get_days_since_epoch()))
+        }
+    }
+
+    impl TryFrom<Time> for NaiveTime {
+        type Error = NmeaError;
+
+        fn try_from(time: Time) -> Result<Self, Self::Error> {
+            NaiveTime::from_num_seconds_from_midnight_opt(
+                time.get_seconds(),
+                time.get_nanoseconds(),
+            )
+            .ok_or(NmeaError::ParseError)
+        }
+    }
+
+    impl TryFrom<DateTime> for chrono::DateTime<Utc> {
+        type Error = NmeaError;
+
+        fn try_from(date_time: DateTime) -> Result<Self, Self::Error> {
+            let date = NaiveDate::from(date_time.date);
+            let time = NaiveTime::try_from(date_time.time)?;
+            Ok(Utc.from_utc_datetime(&date.and_time(time)))
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_conversions {
+    use super::*;
+    use crate::NmeaError;
+
+    impl From<Date> for time::Date {
+        fn from(date: Date) -> Self {
+            time::OffsetDateTime::UNIX_EPOCH.date()
+                + time::Duration::days(i64::from(date.get_days_since_epoch()))
+        }
+    }
+
+    impl TryFrom<Time> for time::Time {
+        type Error = NmeaError;
+
+        fn try_from(time: Time) -> Result<Self, Self::Error> {
+            let (hour, minute, second) = time.get_hour_minute_second();
+            time::Time::from_hms_nano(hour, minute, second, time.get_nanoseconds())
+                .map_err(|_| NmeaError::ParseError)
+        }
+    }
+
+    impl TryFrom<DateTime> for time::OffsetDateTime {
+        type Error = NmeaError;
+
+        fn try_from(date_time: DateTime) -> Result<Self, Self::Error> {
+            let date = time::Date::from(date_time.date);
+            let time = time::Time::try_from(date_time.time)?;
+            Ok(time::PrimitiveDateTime::new(date, time).assume_utc())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_days_to_calendar_date() {
+        assert_eq!((1970, 1, 1), Date::new(0).get_year_month_day());
+        assert_eq!((2023, 9, 25), Date::new(19625).get_year_month_day());
+        assert_eq!((2024, 2, 29), Date::new(19782).get_year_month_day());
+    }
+
+    #[test]
+    fn keeps_sub_second_resolution() {
+        let time = Time::new(28034565);
+        assert_eq!((0, 46, 43), time.get_hour_minute_second());
+        assert_eq!(456_500_000, time.get_nanoseconds());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn converts_to_chrono() {
+        let date_time = DateTime {
+            date: Date::new(19625),
+            time: Time::new(28034565),
+        };
+        let converted = chrono::DateTime::<chrono::Utc>::try_from(date_time).unwrap();
+        assert_eq!(
+            chrono::NaiveDate::from_ymd_opt(2023, 9, 25)
+                .unwrap()
+                .and_hms_micro_opt(0, 46, 43, 456_500)
+                .unwrap(),
+            converted.naive_utc()
+        );
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn converts_to_time() {
+        let date_time = DateTime {
+            date: Date::new(19625),
+            time: Time::new(28034565),
+        };
+        let converted = time::OffsetDateTime::try_from(date_time).unwrap();
+        assert_eq!(2023, converted.year());
+        assert_eq!(456_500_000, converted.nanosecond());
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{
+    date::{Date, DateTime, Time},
+    field_value::FieldValue,
+    pgn_fields::DistanceLogFields,
+    units::Distance,
+    Message, NmeaError,
+};
+
+/// PGN 128275, a fast packet message.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DistanceLog {
+    pub date: FieldValue<Date>,
+    pub time: FieldValue<Time>,
+    /// Total distance travelled through the water.
+    pub log: FieldValue<Distance>,
+    /// Distance travelled through the water since the trip log was reset.
+    pub trip_log: FieldValue<Distance>,
+}
+
+impl DistanceLog {
+    pub fn get_date_time(&self) -> Option<DateTime> {
+        Some(DateTime {
+            date: self.date.value()?,
+            time: self.time.value()?,
+        })
+    }
+}
+
+impl Message<DistanceLog, &[u8]> for DistanceLog {
+    fn get_data(data: &[u8]) -> Result<DistanceLog, NmeaError> {
+        let fields = DistanceLogFields::get_data(data)?;
+        Ok(DistanceLog {
+            date: fields.date.map(|days| Date::new(days as u16)),
+            time: fields.time.map(Time::from_seconds),
+            log: fields.log.map(Distance::from_meters),
+            trip_log: fields.trip_log.map(Distance::from_meters),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_distance_log() {
+        let data = [
+            0xA9, 0x4C, 0xFF, 0xFF, 0xFF, 0xFF, 0x40, 0xE2, 0x01, 0x00, 0xD2, 0x04, 0x00, 0x00,
+        ];
+        let distance_log = DistanceLog::get_data(&data).unwrap();
+        assert_eq!(
+            19625,
+            distance_log.date.value().unwrap().get_days_since_epoch()
+        );
+        assert_eq!(FieldValue::NotAvailable, distance_log.time);
+        assert_eq!(123456.0, distance_log.log.value().unwrap().get_meters());
+        assert_eq!(1234.0, distance_log.trip_log.value().unwrap().get_meters());
+    }
+}
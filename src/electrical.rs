@@ -0,0 +1,229 @@
+use core::time::Duration;
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use crate::{
+    field_value::FieldValue,
+    nmea_frame::{BatteryStatusFrame, InverterStatusFrame},
+    pgn_fields::{
+        BatteryStatusFields, ChargerStatusFields, DCDetailedStatusFields, InverterStatusFields,
+    },
+    units::{Current, Temperature, Voltage},
+    Message, NmeaError,
+};
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum DCType {
+    Battery = 0,
+    Alternator = 1,
+    Converter = 2,
+    SolarCell = 3,
+    WindGenerator = 4,
+}
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum ChargerState {
+    NotCharging = 0,
+    Bulk = 1,
+    Absorption = 2,
+    Overcharge = 3,
+    Equalise = 4,
+    Float = 5,
+    NoFloat = 6,
+    ConstantVoltageCurrent = 7,
+    Disabled = 8,
+    Fault = 9,
+}
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum ChargerMode {
+    Standalone = 0,
+    Primary = 1,
+    Secondary = 2,
+    Echo = 3,
+}
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum InverterState {
+    Invert = 0,
+    AcPassthrough = 1,
+    LoadSense = 2,
+    Fault = 3,
+    Disabled = 4,
+}
+
+/// PGN 127508
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BatteryStatus {
+    pub instance: u8,
+    pub voltage: FieldValue<Voltage>,
+    /// Positive while charging.
+    pub current: FieldValue<Current>,
+    pub temperature: FieldValue<Temperature>,
+    pub sid: FieldValue<u8>,
+}
+
+impl Message<BatteryStatus, BatteryStatusFrame> for BatteryStatus {
+    fn get_data(frame: BatteryStatusFrame) -> Result<BatteryStatus, NmeaError> {
+        let fields = BatteryStatusFields::get_data(&frame.data)?;
+        Ok(BatteryStatus {
+            instance: fields.instance.to_instance(),
+            voltage: fields
+                .voltage
+                .map(|voltage| Voltage::from_volts(voltage as f32)),
+            current: fields
+                .current
+                .map(|current| Current::from_amperes(current as f32)),
+            temperature: fields
+                .temperature
+                .map(|temperature| Temperature::from_kelvin(temperature as f32)),
+            sid: fields.sid.map(|sid| sid as u8),
+        })
+    }
+}
+
+/// PGN 127506, a fast packet message.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DCDetailedStatus {
+    pub sid: FieldValue<u8>,
+    pub instance: u8,
+    pub dc_type: Option<DCType>,
+    /// Percent
+    pub state_of_charge: FieldValue<u8>,
+    /// Percent
+    pub state_of_health: FieldValue<u8>,
+    pub time_remaining: FieldValue<Duration>,
+    pub ripple_voltage: FieldValue<Voltage>,
+    /// Ampere hours
+    pub remaining_capacity: FieldValue<u16>,
+}
+
+impl Message<DCDetailedStatus, &[u8]> for DCDetailedStatus {
+    fn get_data(data: &[u8]) -> Result<DCDetailedStatus, NmeaError> {
+        let fields = DCDetailedStatusFields::get_data(data)?;
+        Ok(DCDetailedStatus {
+            sid: fields.sid.map(|sid| sid as u8),
+            instance: fields.instance.to_instance(),
+            dc_type: fields.dc_type.value().and_then(FromPrimitive::from_f64),
+            state_of_charge: fields.state_of_charge.map(|charge| charge as u8),
+            state_of_health: fields.state_of_health.map(|health| health as u8),
+            time_remaining: fields.time_remaining.map(Duration::from_secs_f64),
+            ripple_voltage: fields
+                .ripple_voltage
+                .map(|voltage| Voltage::from_volts(voltage as f32)),
+            remaining_capacity: fields.remaining_capacity.map(|capacity| capacity as u16),
+        })
+    }
+}
+
+/// PGN 127507, a fast packet message.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ChargerStatus {
+    pub instance: u8,
+    pub battery_instance: u8,
+    pub operating_state: Option<ChargerState>,
+    pub charge_mode: Option<ChargerMode>,
+    pub enabled: FieldValue<bool>,
+    pub equalization_pending: FieldValue<bool>,
+    pub equalization_time_remaining: FieldValue<Duration>,
+}
+
+impl Message<ChargerStatus, &[u8]> for ChargerStatus {
+    fn get_data(data: &[u8]) -> Result<ChargerStatus, NmeaError> {
+        let fields = ChargerStatusFields::get_data(data)?;
+        Ok(ChargerStatus {
+            instance: fields.instance.to_instance(),
+            battery_instance: fields.battery_instance.to_instance(),
+            operating_state: fields
+                .operating_state
+                .value()
+                .and_then(FromPrimitive::from_f64),
+            charge_mode: fields.charge_mode.value().and_then(FromPrimitive::from_f64),
+            enabled: fields.enabled.to_status(),
+            equalization_pending: fields.equalization_pending.to_status(),
+            equalization_time_remaining: fields
+                .equalization_time_remaining
+                .map(Duration::from_secs_f64),
+        })
+    }
+}
+
+/// PGN 127509
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct InverterStatus {
+    pub instance: u8,
+    pub ac_instance: u8,
+    pub dc_instance: u8,
+    pub operating_state: Option<InverterState>,
+    pub enabled: FieldValue<bool>,
+}
+
+impl Message<InverterStatus, InverterStatusFrame> for InverterStatus {
+    fn get_data(frame: InverterStatusFrame) -> Result<InverterStatus, NmeaError> {
+        let fields = InverterStatusFields::get_data(&frame.data)?;
+        Ok(InverterStatus {
+            instance: fields.instance.to_instance(),
+            ac_instance: fields.ac_instance.to_instance(),
+            dc_instance: fields.dc_instance.to_instance(),
+            operating_state: fields
+                .operating_state
+                .value()
+                .and_then(FromPrimitive::from_f64),
+            enabled: fields.enabled.to_status(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_battery_status() {
+        let data = [1, 0x0C, 0x05, 0x9C, 0xFF, 0x54, 0x72, 7];
+        let battery_status = BatteryStatus::get_data(BatteryStatusFrame { data }).unwrap();
+        assert_eq!(1, battery_status.instance);
+        assert_eq!(12.92, battery_status.voltage.value().unwrap().get_volts());
+        assert_eq!(-10.0, battery_status.current.value().unwrap().get_amperes());
+        assert_eq!(
+            292.68,
+            battery_status.temperature.value().unwrap().get_kelvin()
+        );
+        assert_eq!(FieldValue::Value(7), battery_status.sid);
+    }
+
+    #[test]
+    fn parses_dc_detailed_status() {
+        let data = [3, 0, 0, 87, 100, 0x2C, 0x01, 0xFF, 0xFF, 0xC8, 0x00];
+        let status = DCDetailedStatus::get_data(&data).unwrap();
+        assert_eq!(Some(DCType::Battery), status.dc_type);
+        assert_eq!(FieldValue::Value(87), status.state_of_charge);
+        assert_eq!(
+            Duration::from_secs(300 * 60),
+            status.time_remaining.value().unwrap()
+        );
+        assert_eq!(FieldValue::NotAvailable, status.ripple_voltage);
+        assert_eq!(FieldValue::Value(200), status.remaining_capacity);
+    }
+
+    #[test]
+    fn parses_charger_status() {
+        let data = [0, 1, 0x12, 0xF1, 0xFF, 0xFF];
+        let status = ChargerStatus::get_data(&data).unwrap();
+        assert_eq!(Some(ChargerState::Absorption), status.operating_state);
+        assert_eq!(Some(ChargerMode::Primary), status.charge_mode);
+        assert_eq!(FieldValue::Value(true), status.enabled);
+        assert_eq!(FieldValue::Value(false), status.equalization_pending);
+        assert_eq!(FieldValue::NotAvailable, status.equalization_time_remaining);
+    }
+
+    #[test]
+    fn parses_inverter_status() {
+        let data = [0, 1, 2, 0x11, 0xFF, 0xFF, 0xFF, 0xFF];
+        let status = InverterStatus::get_data(InverterStatusFrame { data }).unwrap();
+        assert_eq!(2, status.dc_instance);
+        assert_eq!(Some(InverterState::AcPassthrough), status.operating_state);
+        assert_eq!(FieldValue::Value(true), status.enabled);
+    }
+}
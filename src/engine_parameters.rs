@@ -0,0 +1,274 @@
+use core::time::Duration;
+
+use bitfield::bitfield;
+
+use crate::{
+    field_value::FieldValue,
+    nmea_frame::EngineParametersRapidUpdateFrame,
+    pgn_fields::{EngineParametersDynamicFields, EngineParametersRapidUpdateFields},
+    units::{FlowRate, Pressure, RotationalSpeed, Temperature, Voltage},
+    Encode, Message, NmeaError,
+};
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct EngineStatus1(u16);
+    impl Debug;
+    pub check_engine, set_check_engine: 0;
+    pub over_temperature, set_over_temperature: 1;
+    pub low_oil_pressure, set_low_oil_pressure: 2;
+    pub low_oil_level, set_low_oil_level: 3;
+    pub low_fuel_pressure, set_low_fuel_pressure: 4;
+    pub low_system_voltage, set_low_system_voltage: 5;
+    pub low_coolant_level, set_low_coolant_level: 6;
+    pub water_flow, set_water_flow: 7;
+    pub water_in_fuel, set_water_in_fuel: 8;
+    pub charge_indicator, set_charge_indicator: 9;
+    pub preheat_indicator, set_preheat_indicator: 10;
+    pub high_boost_pressure, set_high_boost_pressure: 11;
+    pub rev_limit_exceeded, set_rev_limit_exceeded: 12;
+    pub egr_system, set_egr_system: 13;
+    pub throttle_position_sensor, set_throttle_position_sensor: 14;
+    pub emergency_stop, set_emergency_stop: 15;
+}
+
+bitfield! {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct EngineStatus2(u16);
+    impl Debug;
+    pub warning_level_1, set_warning_level_1: 0;
+    pub warning_level_2, set_warning_level_2: 1;
+    pub power_reduction, set_power_reduction: 2;
+    pub maintenance_needed, set_maintenance_needed: 3;
+    pub engine_comm_error, set_engine_comm_error: 4;
+    pub secondary_throttle, set_secondary_throttle: 5;
+    pub neutral_start_protect, set_neutral_start_protect: 6;
+    pub engine_shutting_down, set_engine_shutting_down: 7;
+}
+
+/// PGN 127488, sent ten times a second per engine.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct EngineParametersRapidUpdate {
+    /// 0 is the single or port engine, 1 the starboard engine.
+    pub instance: u8,
+    pub speed: FieldValue<RotationalSpeed>,
+    pub boost_pressure: FieldValue<Pressure>,
+    /// Percent, negative is trimmed down.
+    pub tilt_trim: FieldValue<i8>,
+}
+
+impl Message<EngineParametersRapidUpdate, EngineParametersRapidUpdateFrame>
+    for EngineParametersRapidUpdate
+{
+    fn get_data(
+        frame: EngineParametersRapidUpdateFrame,
+    ) -> Result<EngineParametersRapidUpdate, NmeaError> {
+        let fields = EngineParametersRapidUpdateFields::get_data(&frame.data)?;
+        Ok(EngineParametersRapidUpdate {
+            instance: fields.instance.to_instance(),
+            speed: fields
+                .speed
+                .map(|speed| RotationalSpeed::from_revolutions_per_minute(speed as f32)),
+            boost_pressure: fields
+                .boost_pressure
+                .map(|pressure| Pressure::from_pascals(pressure as f32)),
+            tilt_trim: fields.tilt_trim.map(|tilt_trim| tilt_trim as i8),
+        })
+    }
+}
+
+impl Encode<EngineParametersRapidUpdateFrame> for EngineParametersRapidUpdate {
+    fn encode(&self) -> EngineParametersRapidUpdateFrame {
+        EngineParametersRapidUpdateFields {
+            instance: FieldValue::from_u8(self.instance).map(f64::from),
+            speed: self
+                .speed
+                .map(|speed| f64::from(speed.get_revolutions_per_minute())),
+            boost_pressure: self
+                .boost_pressure
+                .map(|pressure| f64::from(pressure.get_pascals())),
+            tilt_trim: self.tilt_trim.map(f64::from),
+        }
+        .to_frame()
+    }
+}
+
+/// PGN 127489, a fast packet message sent twice a second per engine.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct EngineParametersDynamic {
+    pub instance: u8,
+    pub oil_pressure: FieldValue<Pressure>,
+    pub oil_temperature: FieldValue<Temperature>,
+    pub coolant_temperature: FieldValue<Temperature>,
+    pub alternator_potential: FieldValue<Voltage>,
+    pub fuel_rate: FieldValue<FlowRate>,
+    pub total_engine_hours: FieldValue<Duration>,
+    pub coolant_pressure: FieldValue<Pressure>,
+    pub fuel_pressure: FieldValue<Pressure>,
+    pub status_1: EngineStatus1,
+    pub status_2: EngineStatus2,
+    /// Percent of the maximum load.
+    pub load: FieldValue<i8>,
+    /// Percent of the maximum torque.
+    pub torque: FieldValue<i8>,
+}
+
+impl Message<EngineParametersDynamic, &[u8]> for EngineParametersDynamic {
+    /// Decodes the payload reassembled by
+    /// [`FastPacketAssembler`](crate::fast_packet::FastPacketAssembler).
+    fn get_data(data: &[u8]) -> Result<EngineParametersDynamic, NmeaError> {
+        let fields = EngineParametersDynamicFields::get_data(data)?;
+        Ok(EngineParametersDynamic {
+            instance: fields.instance.to_instance(),
+            oil_pressure: fields
+                .oil_pressure
+                .map(|pressure| Pressure::from_pascals(pressure as f32)),
+            oil_temperature: fields
+                .oil_temperature
+                .map(|temperature| Temperature::from_kelvin(temperature as f32)),
+            coolant_temperature: fields
+                .temperature
+                .map(|temperature| Temperature::from_kelvin(temperature as f32)),
+            alternator_potential: fields
+                .alternator_potential
+                .map(|potential| Voltage::from_volts(potential as f32)),
+            fuel_rate: fields
+                .fuel_rate
+                .map(|rate| FlowRate::from_liters_per_hour(rate as f32)),
+            total_engine_hours: fields
+                .total_engine_hours
+                .map(|seconds| Duration::from_secs(seconds as u64)),
+            coolant_pressure: fields
+                .coolant_pressure
+                .map(|pressure| Pressure::from_pascals(pressure as f32)),
+            fuel_pressure: fields
+                .fuel_pressure
+                .map(|pressure| Pressure::from_pascals(pressure as f32)),
+            status_1: EngineStatus1(
+                fields
+                    .discrete_status1
+                    .value()
+                    .map_or(u16::MAX, |status| status as u16),
+            ),
+            status_2: EngineStatus2(
+                fields
+                    .discrete_status2
+                    .value()
+                    .map_or(u16::MAX, |status| status as u16),
+            ),
+            load: fields.engine_load.map(|load| load as i8),
+            torque: fields.engine_torque.map(|torque| torque as i8),
+        })
+    }
+}
+
+impl Encode<Vec<u8>> for EngineParametersDynamic {
+    fn encode(&self) -> Vec<u8> {
+        EngineParametersDynamicFields {
+            instance: FieldValue::from_u8(self.instance).map(f64::from),
+            oil_pressure: self
+                .oil_pressure
+                .map(|pressure| f64::from(pressure.get_pascals())),
+            oil_temperature: self
+                .oil_temperature
+                .map(|temperature| f64::from(temperature.get_kelvin())),
+            temperature: self
+                .coolant_temperature
+                .map(|temperature| f64::from(temperature.get_kelvin())),
+            alternator_potential: self
+                .alternator_potential
+                .map(|potential| f64::from(potential.get_volts())),
+            fuel_rate: self
+                .fuel_rate
+                .map(|rate| f64::from(rate.get_liters_per_hour())),
+            total_engine_hours: self.total_engine_hours.map(|hours| hours.as_secs() as f64),
+            coolant_pressure: self
+                .coolant_pressure
+                .map(|pressure| f64::from(pressure.get_pascals())),
+            fuel_pressure: self
+                .fuel_pressure
+                .map(|pressure| f64::from(pressure.get_pascals())),
+            discrete_status1: FieldValue::Value(f64::from(self.status_1.0)),
+            discrete_status2: FieldValue::Value(f64::from(self.status_2.0)),
+            engine_load: self.load.map(f64::from),
+            engine_torque: self.torque.map(f64::from),
+        }
+        .encode()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fast_packet, fast_packet::FastPacketAssembler, NmeaId};
+
+    #[test]
+    fn parses_engine_parameters_rapid_update() {
+        let data = [0, 0x40, 0x1F, 0x2C, 0x01, 0xFB, 0xFF, 0xFF];
+        let parsed_data =
+            EngineParametersRapidUpdate::get_data(EngineParametersRapidUpdateFrame { data });
+        let rapid_update = parsed_data.unwrap();
+        assert_eq!(0, rapid_update.instance);
+        assert_eq!(
+            2000.0,
+            rapid_update
+                .speed
+                .value()
+                .unwrap()
+                .get_revolutions_per_minute()
+        );
+        assert_eq!(
+            30000.0,
+            rapid_update.boost_pressure.value().unwrap().get_pascals()
+        );
+        assert_eq!(FieldValue::Value(-5), rapid_update.tilt_trim);
+        assert_eq!(data, rapid_update.encode().data);
+    }
+
+    #[test]
+    fn round_trips_engine_parameters_dynamic_over_fast_packet() {
+        let mut status_1 = EngineStatus1(0);
+        status_1.set_low_oil_pressure(true);
+        let mut status_2 = EngineStatus2(0);
+        status_2.set_maintenance_needed(true);
+        let dynamic = EngineParametersDynamic {
+            instance: 1,
+            oil_pressure: FieldValue::Value(Pressure::from_pascals(350000.0)),
+            oil_temperature: FieldValue::NotAvailable,
+            coolant_temperature: FieldValue::Value(Temperature::from_kelvin(355.0)),
+            alternator_potential: FieldValue::Value(Voltage::from_volts(14.2)),
+            fuel_rate: FieldValue::Value(FlowRate::from_liters_per_hour(12.5)),
+            total_engine_hours: FieldValue::Value(Duration::from_secs(3600 * 812)),
+            coolant_pressure: FieldValue::NotAvailable,
+            fuel_pressure: FieldValue::NotAvailable,
+            status_1,
+            status_2,
+            load: FieldValue::Value(42),
+            torque: FieldValue::NotAvailable,
+        };
+
+        let id = NmeaId(0x09F20100);
+        let mut assembler = FastPacketAssembler::new();
+        let payload = fast_packet::encode(0, &dynamic.encode())
+            .unwrap()
+            .iter()
+            .find_map(|frame| assembler.push(&id, frame))
+            .unwrap();
+        assert_eq!(26, payload.len());
+
+        let parsed = EngineParametersDynamic::get_data(&payload).unwrap();
+        assert_eq!(
+            355.0,
+            parsed.coolant_temperature.value().unwrap().get_kelvin()
+        );
+        assert_eq!(FieldValue::NotAvailable, parsed.oil_temperature);
+        assert!(parsed.status_1.low_oil_pressure());
+        assert!(!parsed.status_1.check_engine());
+        assert!(parsed.status_2.maintenance_needed());
+        assert_eq!(FieldValue::Value(42), parsed.load);
+        assert_eq!(
+            Duration::from_secs(3600 * 812),
+            parsed.total_engine_hours.value().unwrap()
+        );
+    }
+}
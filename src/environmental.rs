@@ -0,0 +1,352 @@
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use crate::{
+    field_value::FieldValue,
+    nmea_frame::{
+        ActualPressureFrame, EnvironmentalParametersFrame, EnvironmentalParametersObsoleteFrame,
+        HumidityFrame, TemperatureExtendedRangeFrame, TemperatureFrame,
+    },
+    pgn_fields::{
+        ActualPressureFields, EnvironmentalParametersFields, EnvironmentalParametersObsoleteFields,
+        HumidityFields, TemperatureExtendedRangeFields, TemperatureFields,
+    },
+    units::{Pressure, Temperature},
+    Message, NmeaError,
+};
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum TemperatureSource {
+    Sea = 0,
+    Outside = 1,
+    Inside = 2,
+    EngineRoom = 3,
+    MainCabin = 4,
+    LiveWell = 5,
+    BaitWell = 6,
+    Refrigeration = 7,
+    HeatingSystem = 8,
+    DewPoint = 9,
+    ApparentWindChill = 10,
+    TheoreticalWindChill = 11,
+    HeatIndex = 12,
+    Freezer = 13,
+    ExhaustGas = 14,
+    ShaftSeal = 15,
+}
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum HumiditySource {
+    Inside = 0,
+    Outside = 1,
+}
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum PressureSource {
+    Atmospheric = 0,
+    Water = 1,
+    Steam = 2,
+    CompressedAir = 3,
+    Hydraulic = 4,
+    Filter = 5,
+    AltimeterSetting = 6,
+    Oil = 7,
+    Fuel = 8,
+}
+
+fn temperature(kelvin: FieldValue<f64>) -> FieldValue<Temperature> {
+    kelvin.map(|kelvin| Temperature::from_kelvin(kelvin as f32))
+}
+
+fn pressure(pascals: FieldValue<f64>) -> FieldValue<Pressure> {
+    pascals.map(|pascals| Pressure::from_pascals(pascals as f32))
+}
+
+/// Relative humidity in percent.
+fn humidity(percent: FieldValue<f64>) -> FieldValue<f32> {
+    percent.map(|percent| percent as f32)
+}
+
+/// PGN 130310, superseded by 130311 but still sent by many devices.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct EnvironmentalParametersObsolete {
+    pub sid: FieldValue<u8>,
+    pub water_temperature: FieldValue<Temperature>,
+    pub outside_temperature: FieldValue<Temperature>,
+    pub atmospheric_pressure: FieldValue<Pressure>,
+}
+
+impl Message<EnvironmentalParametersObsolete, EnvironmentalParametersObsoleteFrame>
+    for EnvironmentalParametersObsolete
+{
+    fn get_data(
+        frame: EnvironmentalParametersObsoleteFrame,
+    ) -> Result<EnvironmentalParametersObsolete, NmeaError> {
+        let fields = EnvironmentalParametersObsoleteFields::get_data(&frame.data)?;
+        Ok(EnvironmentalParametersObsolete {
+            sid: fields.sid.map(|sid| sid as u8),
+            water_temperature: temperature(fields.water_temperature),
+            outside_temperature: temperature(fields.outside_ambient_air_temperature),
+            atmospheric_pressure: pressure(fields.atmospheric_pressure),
+        })
+    }
+}
+
+/// PGN 130311
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct EnvironmentalParameters {
+    pub sid: FieldValue<u8>,
+    pub temperature_source: Option<TemperatureSource>,
+    pub humidity_source: Option<HumiditySource>,
+    pub temperature: FieldValue<Temperature>,
+    /// Relative humidity in percent.
+    pub humidity: FieldValue<f32>,
+    pub atmospheric_pressure: FieldValue<Pressure>,
+}
+
+impl Message<EnvironmentalParameters, EnvironmentalParametersFrame> for EnvironmentalParameters {
+    fn get_data(frame: EnvironmentalParametersFrame) -> Result<EnvironmentalParameters, NmeaError> {
+        let fields = EnvironmentalParametersFields::get_data(&frame.data)?;
+        Ok(EnvironmentalParameters {
+            sid: fields.sid.map(|sid| sid as u8),
+            temperature_source: fields
+                .temperature_source
+                .value()
+                .and_then(FromPrimitive::from_f64),
+            humidity_source: fields
+                .humidity_source
+                .value()
+                .and_then(FromPrimitive::from_f64),
+            temperature: temperature(fields.temperature),
+            humidity: humidity(fields.humidity),
+            atmospheric_pressure: pressure(fields.atmospheric_pressure),
+        })
+    }
+}
+
+/// PGN 130312
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TemperatureMeasurement {
+    pub sid: FieldValue<u8>,
+    pub instance: u8,
+    pub source: Option<TemperatureSource>,
+    pub actual_temperature: FieldValue<Temperature>,
+    pub set_temperature: FieldValue<Temperature>,
+}
+
+impl Message<TemperatureMeasurement, TemperatureFrame> for TemperatureMeasurement {
+    fn get_data(frame: TemperatureFrame) -> Result<TemperatureMeasurement, NmeaError> {
+        let fields = TemperatureFields::get_data(&frame.data)?;
+        Ok(TemperatureMeasurement {
+            sid: fields.sid.map(|sid| sid as u8),
+            instance: fields.instance.to_instance(),
+            source: fields.source.value().and_then(FromPrimitive::from_f64),
+            actual_temperature: temperature(fields.actual_temperature),
+            set_temperature: temperature(fields.set_temperature),
+        })
+    }
+}
+
+/// PGN 130313
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Humidity {
+    pub sid: FieldValue<u8>,
+    pub instance: u8,
+    pub source: Option<HumiditySource>,
+    /// Relative humidity in percent.
+    pub actual_humidity: FieldValue<f32>,
+    /// Relative humidity in percent.
+    pub set_humidity: FieldValue<f32>,
+}
+
+impl Message<Humidity, HumidityFrame> for Humidity {
+    fn get_data(frame: HumidityFrame) -> Result<Humidity, NmeaError> {
+        let fields = HumidityFields::get_data(&frame.data)?;
+        Ok(Humidity {
+            sid: fields.sid.map(|sid| sid as u8),
+            instance: fields.instance.to_instance(),
+            source: fields.source.value().and_then(FromPrimitive::from_f64),
+            actual_humidity: humidity(fields.actual_humidity),
+            set_humidity: humidity(fields.set_humidity),
+        })
+    }
+}
+
+/// PGN 130314
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ActualPressure {
+    pub sid: FieldValue<u8>,
+    pub instance: u8,
+    pub source: Option<PressureSource>,
+    pub pressure: FieldValue<Pressure>,
+}
+
+impl Message<ActualPressure, ActualPressureFrame> for ActualPressure {
+    fn get_data(frame: ActualPressureFrame) -> Result<ActualPressure, NmeaError> {
+        let fields = ActualPressureFields::get_data(&frame.data)?;
+        Ok(ActualPressure {
+            sid: fields.sid.map(|sid| sid as u8),
+            instance: fields.instance.to_instance(),
+            source: fields.source.value().and_then(FromPrimitive::from_f64),
+            pressure: pressure(fields.pressure),
+        })
+    }
+}
+
+/// PGN 130316, a higher resolution replacement for 130312 that also covers
+/// exhaust gas temperatures.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TemperatureExtendedRange {
+    pub sid: FieldValue<u8>,
+    pub instance: u8,
+    pub source: Option<TemperatureSource>,
+    pub temperature: FieldValue<Temperature>,
+    pub set_temperature: FieldValue<Temperature>,
+}
+
+impl Message<TemperatureExtendedRange, TemperatureExtendedRangeFrame> for TemperatureExtendedRange {
+    fn get_data(
+        frame: TemperatureExtendedRangeFrame,
+    ) -> Result<TemperatureExtendedRange, NmeaError> {
+        let fields = TemperatureExtendedRangeFields::get_data(&frame.data)?;
+        Ok(TemperatureExtendedRange {
+            sid: fields.sid.map(|sid| sid as u8),
+            instance: fields.instance.to_instance(),
+            source: fields.source.value().and_then(FromPrimitive::from_f64),
+            temperature: temperature(fields.temperature),
+            set_temperature: temperature(fields.set_temperature),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_environmental_parameters() {
+        // Outside temperature and humidity, 20 °C, 50 % and 1013 hPa
+        let data = [0, 0x41, 0x83, 0x72, 0x6A, 0x30, 0xF5, 0x03];
+        let parameters =
+            EnvironmentalParameters::get_data(EnvironmentalParametersFrame { data }).unwrap();
+        assert_eq!(
+            Some(TemperatureSource::Outside),
+            parameters.temperature_source
+        );
+        assert_eq!(Some(HumiditySource::Outside), parameters.humidity_source);
+        assert_eq!(
+            20.0,
+            parameters
+                .temperature
+                .value()
+                .unwrap()
+                .get_celsius()
+                .round()
+        );
+        assert_eq!(50.0, parameters.humidity.value().unwrap().round());
+        assert_eq!(
+            101300.0,
+            parameters
+                .atmospheric_pressure
+                .value()
+                .unwrap()
+                .get_pascals()
+        );
+    }
+
+    #[test]
+    fn parses_obsolete_environmental_parameters() {
+        // Sea at 15 °C, air at 20 °C and 1013 hPa
+        let data = [0, 0x8F, 0x70, 0x83, 0x72, 0xF5, 0x03, 0xFF];
+        let parameters =
+            EnvironmentalParametersObsolete::get_data(EnvironmentalParametersObsoleteFrame {
+                data,
+            })
+            .unwrap();
+        assert_eq!(FieldValue::Value(0), parameters.sid);
+        assert_eq!(
+            15.0,
+            parameters
+                .water_temperature
+                .value()
+                .unwrap()
+                .get_celsius()
+                .round()
+        );
+        assert_eq!(
+            20.0,
+            parameters
+                .outside_temperature
+                .value()
+                .unwrap()
+                .get_celsius()
+                .round()
+        );
+        assert_eq!(
+            101300.0,
+            parameters
+                .atmospheric_pressure
+                .value()
+                .unwrap()
+                .get_pascals()
+        );
+    }
+
+    #[test]
+    fn parses_humidity() {
+        // 62.5 % outside, set point -1 % to check the sign
+        let data = [5, 2, 1, 0x09, 0x3D, 0x06, 0xFF, 0xFF];
+        let humidity = Humidity::get_data(HumidityFrame { data }).unwrap();
+        assert_eq!(FieldValue::Value(5), humidity.sid);
+        assert_eq!(2, humidity.instance);
+        assert_eq!(Some(HumiditySource::Outside), humidity.source);
+        assert_eq!(
+            625.0,
+            (humidity.actual_humidity.value().unwrap() * 10.0).round()
+        );
+        assert_eq!(-1.0, humidity.set_humidity.value().unwrap().round());
+
+        let data = [5, 2, 0, 0xFF, 0x7F, 0xFF, 0x7F, 0xFF];
+        let humidity = Humidity::get_data(HumidityFrame { data }).unwrap();
+        assert_eq!(Some(HumiditySource::Inside), humidity.source);
+        assert_eq!(FieldValue::NotAvailable, humidity.actual_humidity);
+        assert_eq!(FieldValue::NotAvailable, humidity.set_humidity);
+    }
+
+    #[test]
+    fn parses_temperature() {
+        let data = [0, 1, 3, 0x83, 0x72, 0xFF, 0xFF, 0xFF];
+        let temperature = TemperatureMeasurement::get_data(TemperatureFrame { data }).unwrap();
+        assert_eq!(Some(TemperatureSource::EngineRoom), temperature.source);
+        assert_eq!(FieldValue::NotAvailable, temperature.set_temperature);
+    }
+
+    #[test]
+    fn parses_actual_pressure() {
+        let data = [0, 0, 0, 0x02, 0x76, 0x0F, 0x00, 0xFF];
+        let pressure = ActualPressure::get_data(ActualPressureFrame { data }).unwrap();
+        assert_eq!(Some(PressureSource::Atmospheric), pressure.source);
+        assert_eq!(
+            101325.0,
+            pressure.pressure.value().unwrap().get_pascals().round()
+        );
+    }
+
+    #[test]
+    fn parses_temperature_extended_range() {
+        // 700 °C exhaust gas
+        let data = [0, 0, 14, 0x5E, 0xD9, 0x0E, 0xFF, 0xFF];
+        let temperature =
+            TemperatureExtendedRange::get_data(TemperatureExtendedRangeFrame { data }).unwrap();
+        assert_eq!(Some(TemperatureSource::ExhaustGas), temperature.source);
+        assert_eq!(
+            700.0,
+            temperature
+                .temperature
+                .value()
+                .unwrap()
+                .get_celsius()
+                .round()
+        );
+    }
+}
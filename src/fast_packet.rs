@@ -0,0 +1,164 @@
+use crate::{NmeaError, NmeaId};
+
+/// Largest payload a fast packet message can carry: 6 bytes in the first
+/// frame and 7 in each of the 31 following ones.
+pub const MAX_PAYLOAD_LENGTH: usize = 223;
+
+struct PartialMessage {
+    source: u32,
+    pgn: u32,
+    sequence: u8,
+    length: usize,
+    next_frame: u8,
+    data: Vec<u8>,
+}
+
+/// Reassembles fast packet messages from their frames. One message can be in
+/// progress per source address and PGN; frames arriving out of order drop
+/// the message.
+pub struct FastPacketAssembler {
+    messages: Vec<PartialMessage>,
+}
+
+impl FastPacketAssembler {
+    pub fn new() -> Self {
+        FastPacketAssembler {
+            messages: Vec::new(),
+        }
+    }
+
+    /// Feeds one frame, returning the payload once the last frame of its
+    /// message has arrived.
+    pub fn push(&mut self, id: &NmeaId, frame: &[u8; 8]) -> Option<Vec<u8>> {
+        let source = id.sa();
+        let pgn = id.get_raw_pgn();
+        let sequence = frame[0] >> 5;
+        let frame_count = frame[0] & 0x1F;
+        let index = self
+            .messages
+            .iter()
+            .position(|message| message.source == source && message.pgn == pgn);
+
+        if frame_count == 0 {
+            if let Some(index) = index {
+                self.messages.remove(index);
+            }
+            let length = usize::from(frame[1]);
+            // A message this long could never be completed
+            if length > MAX_PAYLOAD_LENGTH {
+                return None;
+            }
+            let mut data = Vec::with_capacity(length);
+            data.extend_from_slice(&frame[2..]);
+            if data.len() >= length {
+                data.truncate(length);
+                return Some(data);
+            }
+            self.messages.push(PartialMessage {
+                source,
+                pgn,
+                sequence,
+                length,
+                next_frame: 1,
+                data,
+            });
+            return None;
+        }
+
+        let index = index?;
+        let message = &mut self.messages[index];
+        if message.sequence != sequence || message.next_frame != frame_count {
+            self.messages.remove(index);
+            return None;
+        }
+        message.data.extend_from_slice(&frame[1..]);
+        message.next_frame += 1;
+        if message.data.len() < message.length {
+            return None;
+        }
+        let mut message = self.messages.remove(index);
+        message.data.truncate(message.length);
+        Some(message.data)
+    }
+}
+
+impl Default for FastPacketAssembler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Splits a payload into fast packet frames. `sequence` (0-7) tells apart
+/// consecutive messages of the same PGN and should be incremented by the
+/// sender for every message. Payloads longer than `MAX_PAYLOAD_LENGTH` fail
+/// with `InvalidLength`.
+pub fn encode(sequence: u8, payload: &[u8]) -> Result<Vec<[u8; 8]>, NmeaError> {
+    if payload.len() > MAX_PAYLOAD_LENGTH {
+        return Err(NmeaError::InvalidLength);
+    }
+    let sequence = (sequence & 0x07) << 5;
+
+    let mut first = [0xFF; 8];
+    first[0] = sequence;
+    first[1] = payload.len() as u8;
+    let head = payload.len().min(6);
+    first[2..2 + head].copy_from_slice(&payload[..head]);
+
+    let mut frames = vec![first];
+    for (index, chunk) in payload[head..].chunks(7).enumerate() {
+        let mut frame = [0xFF; 8];
+        frame[0] = sequence | (index as u8 + 1);
+        frame[1..1 + chunk.len()].copy_from_slice(chunk);
+        frames.push(frame);
+    }
+    Ok(frames)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reassembles_encoded_frames() {
+        let id = NmeaId(0x09F20100);
+        let payload: Vec<u8> = (0..26).collect();
+        let frames = encode(3, &payload).unwrap();
+        assert_eq!(4, frames.len());
+
+        let mut assembler = FastPacketAssembler::new();
+        let mut result = None;
+        for frame in frames.iter() {
+            result = assembler.push(&id, frame);
+        }
+        assert_eq!(Some(payload), result);
+    }
+
+    #[test]
+    fn drops_message_with_missing_frame() {
+        let id = NmeaId(0x09F20100);
+        let payload: Vec<u8> = (0..26).collect();
+        let frames = encode(1, &payload).unwrap();
+
+        let mut assembler = FastPacketAssembler::new();
+        assert_eq!(None, assembler.push(&id, &frames[0]));
+        assert_eq!(None, assembler.push(&id, &frames[2]));
+        assert_eq!(None, assembler.push(&id, &frames[3]));
+    }
+
+    #[test]
+    fn rejects_payload_longer_than_fast_packet_allows() {
+        assert_eq!(32, encode(0, &[0; MAX_PAYLOAD_LENGTH]).unwrap().len());
+        assert!(matches!(
+            encode(0, &[0; MAX_PAYLOAD_LENGTH + 1]),
+            Err(NmeaError::InvalidLength)
+        ));
+    }
+
+    #[test]
+    fn ignores_message_longer_than_fast_packet_allows() {
+        let id = NmeaId(0x09F20100);
+        let mut assembler = FastPacketAssembler::new();
+        assert_eq!(None, assembler.push(&id, &[0x20, 233, 0, 1, 2, 3, 4, 5]));
+        assert!(assembler.messages.is_empty());
+    }
+}
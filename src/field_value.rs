@@ -0,0 +1,143 @@
+/// A decoded field, taking into account the values NMEA 2000 reserves at the
+/// top of each field's range.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum FieldValue<T> {
+    Value(T),
+    /// The sender has no data for this field (all bits set).
+    NotAvailable,
+    /// The sender has data but it does not fit in the field.
+    OutOfRange,
+    Reserved,
+    /// The sender reports a fault for this field. Only two bit status fields
+    /// can carry this.
+    Error,
+}
+
+impl<T> FieldValue<T> {
+    pub fn value(self) -> Option<T> {
+        match self {
+            FieldValue::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        matches!(self, FieldValue::Value(_))
+    }
+
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> FieldValue<U> {
+        match self {
+            FieldValue::Value(value) => FieldValue::Value(f(value)),
+            FieldValue::NotAvailable => FieldValue::NotAvailable,
+            FieldValue::OutOfRange => FieldValue::OutOfRange,
+            FieldValue::Reserved => FieldValue::Reserved,
+            FieldValue::Error => FieldValue::Error,
+        }
+    }
+}
+
+macro_rules! raw_field {
+    ($from:ident, $to:ident, $type:ty) => {
+        impl FieldValue<$type> {
+            /// Classifies a raw field: max is not available, max - 1 is out of
+            /// range and max - 2 is reserved.
+            pub fn $from(raw: $type) -> FieldValue<$type> {
+                match raw {
+                    <$type>::MAX => FieldValue::NotAvailable,
+                    raw if raw == <$type>::MAX - 1 => FieldValue::OutOfRange,
+                    raw if raw == <$type>::MAX - 2 => FieldValue::Reserved,
+                    raw => FieldValue::Value(raw),
+                }
+            }
+
+            /// The raw field to send for this value.
+            pub fn $to(self) -> $type {
+                match self {
+                    FieldValue::Value(raw) => raw,
+                    FieldValue::NotAvailable => <$type>::MAX,
+                    FieldValue::OutOfRange | FieldValue::Error => <$type>::MAX - 1,
+                    FieldValue::Reserved => <$type>::MAX - 2,
+                }
+            }
+        }
+    };
+}
+
+raw_field!(from_u8, to_u8, u8);
+raw_field!(from_i8, to_i8, i8);
+raw_field!(from_u16, to_u16, u16);
+raw_field!(from_u32, to_u32, u32);
+raw_field!(from_i16, to_i16, i16);
+raw_field!(from_i32, to_i32, i32);
+
+impl FieldValue<u32> {
+    pub fn from_u24(raw: u32) -> FieldValue<u32> {
+        match raw {
+            0xFFFFFF => FieldValue::NotAvailable,
+            0xFFFFFE => FieldValue::OutOfRange,
+            0xFFFFFD => FieldValue::Reserved,
+            raw => FieldValue::Value(raw),
+        }
+    }
+}
+
+impl FieldValue<bool> {
+    /// Two bit status fields: 0 is no, 1 is yes, 2 is an error and 3 is not
+    /// available.
+    pub fn from_two_bits(raw: u8) -> FieldValue<bool> {
+        match raw {
+            0 => FieldValue::Value(false),
+            1 => FieldValue::Value(true),
+            2 => FieldValue::Error,
+            _ => FieldValue::NotAvailable,
+        }
+    }
+
+    pub fn to_two_bits(self) -> u8 {
+        match self {
+            FieldValue::Value(false) => 0,
+            FieldValue::Value(true) => 1,
+            FieldValue::NotAvailable => 3,
+            _ => 2,
+        }
+    }
+}
+
+impl FieldValue<f64> {
+    /// A two bit status decoded from its field layout, where it is a lookup.
+    pub fn to_status(self) -> FieldValue<bool> {
+        self.value().map_or(FieldValue::NotAvailable, |raw| {
+            FieldValue::from_two_bits(raw as u8)
+        })
+    }
+
+    /// An instance number, kept as a plain byte. Instances the sender did
+    /// not fill in read as 255.
+    pub fn to_instance(self) -> u8 {
+        self.value().map_or(u8::MAX, |instance| instance as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_reserved_values() {
+        assert_eq!(FieldValue::NotAvailable, FieldValue::from_u16(0xFFFF));
+        assert_eq!(FieldValue::OutOfRange, FieldValue::from_u16(0xFFFE));
+        assert_eq!(FieldValue::Reserved, FieldValue::from_u16(0xFFFD));
+        assert_eq!(FieldValue::Value(0xFFFC), FieldValue::from_u16(0xFFFC));
+        assert_eq!(FieldValue::NotAvailable, FieldValue::from_i16(0x7FFF));
+        assert_eq!(FieldValue::Value(-1), FieldValue::from_i16(-1));
+        assert_eq!(0x7FFE, FieldValue::<i16>::OutOfRange.to_i16());
+    }
+
+    #[test]
+    fn classifies_two_bit_status() {
+        assert_eq!(FieldValue::Value(true), FieldValue::from_two_bits(1));
+        assert_eq!(FieldValue::Error, FieldValue::from_two_bits(2));
+        assert_eq!(FieldValue::NotAvailable, FieldValue::from_two_bits(3));
+        assert_eq!(2, FieldValue::Error.to_two_bits());
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use crate::{
+    field_value::FieldValue, nmea_frame::FluidLevelFrame, pgn_fields::FluidLevelFields,
+    units::Volume, Encode, Message, NmeaError,
+};
+
+#[derive(FromPrimitive, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum FluidType {
+    Fuel = 0,
+    FreshWater = 1,
+    GreyWater = 2,
+    LiveWell = 3,
+    Oil = 4,
+    BlackWater = 5,
+    FuelGasoline = 6,
+    Error = 14,
+}
+
+/// PGN 127505
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct FluidLevel {
+    /// Tank instance, 0 to 15 for each fluid type.
+    pub instance: u8,
+    pub fluid_type: Option<FluidType>,
+    /// Percent of the capacity.
+    pub level: FieldValue<f32>,
+    pub capacity: FieldValue<Volume>,
+}
+
+impl FluidLevel {
+    pub fn get_remaining_volume(&self) -> Option<Volume> {
+        let level = self.level.value()?;
+        let capacity = self.capacity.value()?;
+        Some(Volume::from_liters(
+            capacity.get_liters() * f64::from(level) / 100.0,
+        ))
+    }
+}
+
+impl Message<FluidLevel, FluidLevelFrame> for FluidLevel {
+    fn get_data(frame: FluidLevelFrame) -> Result<FluidLevel, NmeaError> {
+        let fields = FluidLevelFields::get_data(&frame.data)?;
+        Ok(FluidLevel {
+            instance: fields
+                .instance
+                .value()
+                .map_or(0x0F, |instance| instance as u8),
+            fluid_type: fields.r#type.value().and_then(FromPrimitive::from_f64),
+            level: fields.level.map(|level| level as f32),
+            capacity: fields.capacity.map(Volume::from_liters),
+        })
+    }
+}
+
+impl Encode<FluidLevelFrame> for FluidLevel {
+    fn encode(&self) -> FluidLevelFrame {
+        FluidLevelFields {
+            instance: FieldValue::Value(f64::from(self.instance & 0x0F)),
+            r#type: self
+                .fluid_type
+                .map_or(FieldValue::NotAvailable, |fluid_type| {
+                    FieldValue::Value(f64::from(fluid_type as u8))
+                }),
+            level: self.level.map(f64::from),
+            capacity: self.capacity.map(|capacity| capacity.get_liters()),
+        }
+        .to_frame()
+    }
+}
+
+/// Identifies a tank: the same instance number is reused for each fluid type
+/// and by each sender.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct TankKey {
+    pub source: u8,
+    pub instance: u8,
+    pub fluid_type: Option<FluidType>,
+}
+
+/// Keeps the latest fluid level reported for every tank on the bus.
+#[derive(Debug)]
+pub struct TankMonitor {
+    levels: HashMap<TankKey, FluidLevel>,
+}
+
+impl TankMonitor {
+    pub fn new() -> Self {
+        TankMonitor {
+            levels: HashMap::new(),
+        }
+    }
+
+    /// Records a fluid level sent by the device at `source`.
+    pub fn update(&mut self, source: u8, fluid_level: FluidLevel) {
+        let key = TankKey {
+            source,
+            instance: fluid_level.instance,
+            fluid_type: fluid_level.fluid_type,
+        };
+        self.levels.insert(key, fluid_level);
+    }
+
+    pub fn get(
+        &self,
+        source: u8,
+        instance: u8,
+        fluid_type: Option<FluidType>,
+    ) -> Option<&FluidLevel> {
+        self.levels.get(&TankKey {
+            source,
+            instance,
+            fluid_type,
+        })
+    }
+
+    pub fn tanks(&self) -> impl Iterator<Item = (&TankKey, &FluidLevel)> {
+        self.levels.iter()
+    }
+
+    /// All tanks holding the given fluid, across instances and senders.
+    pub fn tanks_of_type(
+        &self,
+        fluid_type: FluidType,
+    ) -> impl Iterator<Item = (&TankKey, &FluidLevel)> {
+        self.levels
+            .iter()
+            .filter(move |(key, _)| key.fluid_type == Some(fluid_type))
+    }
+}
+
+impl Default for TankMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fluid_level() {
+        // Fresh water tank 2, 75 % of 200 l
+        let data = [0x12, 0x3E, 0x49, 0xD0, 0x07, 0x00, 0x00, 0xFF];
+        let fluid_level = FluidLevel::get_data(FluidLevelFrame { data }).unwrap();
+        assert_eq!(2, fluid_level.instance);
+        assert_eq!(Some(FluidType::FreshWater), fluid_level.fluid_type);
+        assert_eq!(75.0, fluid_level.level.value().unwrap());
+        assert_eq!(200.0, fluid_level.capacity.value().unwrap().get_liters());
+        assert_eq!(
+            150.0,
+            fluid_level.get_remaining_volume().unwrap().get_liters()
+        );
+        assert_eq!(data, fluid_level.encode().data);
+    }
+
+    #[test]
+    fn keeps_latest_level_per_tank() {
+        let level = |instance, fluid_type, level| FluidLevel {
+            instance,
+            fluid_type: Some(fluid_type),
+            level: FieldValue::Value(level),
+            capacity: FieldValue::NotAvailable,
+        };
+        let mut monitor = TankMonitor::new();
+        monitor.update(10, level(0, FluidType::Fuel, 80.0));
+        monitor.update(10, level(0, FluidType::FreshWater, 40.0));
+        monitor.update(11, level(0, FluidType::Fuel, 20.0));
+        monitor.update(10, level(0, FluidType::Fuel, 79.5));
+
+        assert_eq!(3, monitor.tanks().count());
+        assert_eq!(2, monitor.tanks_of_type(FluidType::Fuel).count());
+        assert_eq!(
+            FieldValue::Value(79.5),
+            monitor.get(10, 0, Some(FluidType::Fuel)).unwrap().level
+        );
+    }
+}
@@ -0,0 +1,646 @@
+//! NMEA Group Function messages (PGN 126208), used to request, command and
+//! read or write the fields of another PGN on a remote device.
+//!
+//! Fields are addressed by their number in the target PGN. Their values are
+//! sent in as many whole bytes as the field needs, so decoding and building
+//! these messages requires the target's field layout from `pgns.json`.
+//! Messages naming fields missing from the layout are kept undecoded.
+
+use core::time::Duration;
+
+use nom::{multi::count, IResult};
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use crate::{
+    field_value::FieldValue,
+    parse::{take_byte, take_bytes, take_u16, take_u24, take_u32, BitInput},
+    pgn::{is_proprietary, pgn_info},
+    proprietary::ProprietaryId,
+    write::FieldWriter,
+    Encode, Message, NmeaError,
+};
+
+/// A field number of the target PGN and its raw value.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FieldPair {
+    pub field: u8,
+    pub value: u64,
+    /// Bytes the value takes on the bus.
+    pub length: u8,
+}
+
+impl FieldPair {
+    /// Pairs `value` with field number `field` of `pgn`, which has to be
+    /// described in `pgns.json`.
+    pub fn new(pgn: u32, field: u8, value: u64) -> Result<FieldPair, NmeaError> {
+        Ok(FieldPair {
+            field,
+            value,
+            length: field_length(pgn, field)?,
+        })
+    }
+
+    /// Pairs a scaled `value` with field number `field` of `pgn`, sent as
+    /// the raw number its resolution and offset give.
+    pub fn from_scaled(pgn: u32, field: u8, value: f64) -> Result<FieldPair, NmeaError> {
+        let info = pgn_info(pgn).ok_or(NmeaError::NotImplemented)?;
+        let raw = info
+            .field(field)
+            .ok_or(NmeaError::NotImplemented)?
+            .to_raw(value);
+        FieldPair::new(pgn, field, raw as u64)
+    }
+
+    /// The value sign extended, for fields that are signed.
+    pub fn get_signed_value(&self) -> i64 {
+        match self.length {
+            0 => 0,
+            1..=7 => {
+                let shift = 64 - u32::from(self.length) * 8;
+                ((self.value << shift) as i64) >> shift
+            }
+            _ => self.value as i64,
+        }
+    }
+}
+
+fn field_length(pgn: u32, field: u8) -> Result<u8, NmeaError> {
+    let info = pgn_info(pgn).ok_or(NmeaError::NotImplemented)?;
+    let field = info.field(field).ok_or(NmeaError::NotImplemented)?;
+    Ok(field.bit_length.div_ceil(8) as u8)
+}
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum PgnErrorCode {
+    Acknowledge = 0,
+    PgnNotSupported = 1,
+    PgnNotAvailable = 2,
+    AccessDenied = 3,
+    RequestOrCommandNotSupported = 4,
+    DefinerTagNotSupported = 5,
+    ReadOrWriteNotSupported = 6,
+}
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum TransmissionIntervalErrorCode {
+    Acknowledge = 0,
+    IntervalNotSupported = 1,
+    IntervalTooLow = 2,
+    AccessDenied = 3,
+    NotSupported = 4,
+}
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum ParameterErrorCode {
+    Acknowledge = 0,
+    InvalidParameterField = 1,
+    TemporaryError = 2,
+    OutOfRange = 3,
+    AccessDenied = 4,
+    NotSupported = 5,
+    ReadOrWriteNotSupported = 6,
+}
+
+/// PGN 126208, a fast packet message.
+#[derive(Debug, PartialEq, Clone)]
+pub enum GroupFunction {
+    /// Asks for the PGN to be sent, optionally at a new interval.
+    Request {
+        pgn: u32,
+        /// `NotAvailable` leaves the interval unchanged, zero restores the
+        /// default.
+        transmission_interval: FieldValue<Duration>,
+        transmission_interval_offset: FieldValue<Duration>,
+        /// Only reply if the fields hold these values.
+        parameters: Vec<FieldPair>,
+    },
+    /// Sets fields of the PGN on the receiving device.
+    Command {
+        pgn: u32,
+        /// `None` leaves the priority unchanged.
+        priority: Option<u8>,
+        parameters: Vec<FieldPair>,
+    },
+    Acknowledge {
+        pgn: u32,
+        pgn_error: Option<PgnErrorCode>,
+        transmission_interval_error: Option<TransmissionIntervalErrorCode>,
+        /// One per parameter of the message being acknowledged.
+        parameter_errors: Vec<Option<ParameterErrorCode>>,
+    },
+    ReadFields {
+        pgn: u32,
+        proprietary: Option<ProprietaryId>,
+        unique_id: u8,
+        selection: Vec<FieldPair>,
+        fields: Vec<u8>,
+    },
+    ReadFieldsReply {
+        pgn: u32,
+        proprietary: Option<ProprietaryId>,
+        unique_id: u8,
+        selection: Vec<FieldPair>,
+        values: Vec<FieldPair>,
+    },
+    WriteFields {
+        pgn: u32,
+        proprietary: Option<ProprietaryId>,
+        unique_id: u8,
+        selection: Vec<FieldPair>,
+        values: Vec<FieldPair>,
+    },
+    WriteFieldsReply {
+        pgn: u32,
+        proprietary: Option<ProprietaryId>,
+        unique_id: u8,
+        selection: Vec<FieldPair>,
+        values: Vec<FieldPair>,
+    },
+    /// Any other function naming a field missing from the PGN's layout in
+    /// `pgns.json`. Everything after the PGN is kept as sent.
+    Unresolved {
+        function_code: u8,
+        pgn: u32,
+        data: Vec<u8>,
+    },
+}
+
+impl GroupFunction {
+    /// A command setting one field, such as a device instance.
+    pub fn command_field(pgn: u32, field: u8, value: u64) -> Result<GroupFunction, NmeaError> {
+        Ok(GroupFunction::Command {
+            pgn,
+            priority: None,
+            parameters: vec![FieldPair::new(pgn, field, value)?],
+        })
+    }
+
+    /// The PGN this group function is about.
+    pub fn get_pgn(&self) -> u32 {
+        match *self {
+            GroupFunction::Request { pgn, .. }
+            | GroupFunction::Command { pgn, .. }
+            | GroupFunction::Acknowledge { pgn, .. }
+            | GroupFunction::ReadFields { pgn, .. }
+            | GroupFunction::ReadFieldsReply { pgn, .. }
+            | GroupFunction::WriteFields { pgn, .. }
+            | GroupFunction::WriteFieldsReply { pgn, .. }
+            | GroupFunction::Unresolved { pgn, .. } => pgn,
+        }
+    }
+}
+
+/// Parses `pairs` field number and value pairs of `pgn`. A field missing
+/// from the layout stops the parse with a `Verify` failure, since its
+/// length is unknown.
+fn take_field_pairs(pgn: u32, pairs: u8) -> impl Fn(BitInput) -> IResult<BitInput, Vec<FieldPair>> {
+    move |mut i: BitInput| {
+        let mut field_pairs = Vec::with_capacity(usize::from(pairs));
+        for _ in 0..pairs {
+            let (rest, field) = take_byte(i)?;
+            let length = field_length(pgn, field).map_err(|_e| {
+                nom::Err::Failure(nom::error::Error::new(i, nom::error::ErrorKind::Verify))
+            })?;
+            let (rest, raw) = take_bytes(usize::from(length))(rest)?;
+            let value = raw
+                .iter()
+                .rev()
+                .fold(0u64, |value, &byte| (value << 8) | u64::from(byte));
+            field_pairs.push(FieldPair {
+                field,
+                value,
+                length,
+            });
+            i = rest;
+        }
+        Ok((i, field_pairs))
+    }
+}
+
+fn take_proprietary_id(pgn: u32) -> impl Fn(BitInput) -> IResult<BitInput, Option<ProprietaryId>> {
+    move |i: BitInput| {
+        if !is_proprietary(pgn) {
+            return Ok((i, None));
+        }
+        let (i, raw) = take_u16(i)?;
+        Ok((i, Some(ProprietaryId::from_raw(raw))))
+    }
+}
+
+type FieldAccess = (
+    u32,
+    Option<ProprietaryId>,
+    u8,
+    Vec<FieldPair>,
+    Vec<FieldPair>,
+);
+
+/// The layout shared by the read fields reply and both write fields
+/// variants: selection pairs followed by value pairs.
+fn parse_field_access(i: BitInput) -> IResult<BitInput, FieldAccess> {
+    let (i, pgn) = take_u24(i)?;
+    let (i, proprietary) = take_proprietary_id(pgn)(i)?;
+    let (i, unique_id) = take_byte(i)?;
+    let (i, selection_pairs) = take_byte(i)?;
+    let (i, value_pairs) = take_byte(i)?;
+    let (i, selection) = take_field_pairs(pgn, selection_pairs)(i)?;
+    let (i, values) = take_field_pairs(pgn, value_pairs)(i)?;
+    Ok((i, (pgn, proprietary, unique_id, selection, values)))
+}
+
+fn parse_group_function(i: BitInput) -> IResult<BitInput, GroupFunction> {
+    let (i, function_code) = take_byte(i)?;
+    match function_code {
+        0 => {
+            let (i, pgn) = take_u24(i)?;
+            let (i, transmission_interval) = take_u32(i)?;
+            let (i, transmission_interval_offset) = take_u16(i)?;
+            let (i, pairs) = take_byte(i)?;
+            let (i, parameters) = take_field_pairs(pgn, pairs)(i)?;
+            let request = GroupFunction::Request {
+                pgn,
+                transmission_interval: FieldValue::from_u32(transmission_interval)
+                    .map(|interval| Duration::from_millis(u64::from(interval))),
+                transmission_interval_offset: FieldValue::from_u16(transmission_interval_offset)
+                    .map(|offset| Duration::from_millis(u64::from(offset) * 10)),
+                parameters,
+            };
+            Ok((i, request))
+        }
+        1 => {
+            let (i, pgn) = take_u24(i)?;
+            let (i, priority) = take_byte(i)?;
+            let (i, pairs) = take_byte(i)?;
+            let (i, parameters) = take_field_pairs(pgn, pairs)(i)?;
+            let priority = priority & 0x0F;
+            let command = GroupFunction::Command {
+                pgn,
+                priority: (priority < 8).then_some(priority),
+                parameters,
+            };
+            Ok((i, command))
+        }
+        2 => {
+            let (i, pgn) = take_u24(i)?;
+            let (i, error_codes) = take_byte(i)?;
+            let (i, parameters) = take_byte(i)?;
+            let (i, raw) = count(take_byte, usize::from(parameters).div_ceil(2))(i)?;
+            let parameter_errors = (0..usize::from(parameters))
+                .map(|index| FromPrimitive::from_u8((raw[index / 2] >> (index % 2 * 4)) & 0x0F))
+                .collect();
+            let acknowledge = GroupFunction::Acknowledge {
+                pgn,
+                pgn_error: FromPrimitive::from_u8(error_codes & 0x0F),
+                transmission_interval_error: FromPrimitive::from_u8(error_codes >> 4),
+                parameter_errors,
+            };
+            Ok((i, acknowledge))
+        }
+        3 => {
+            let (i, pgn) = take_u24(i)?;
+            let (i, proprietary) = take_proprietary_id(pgn)(i)?;
+            let (i, unique_id) = take_byte(i)?;
+            let (i, selection_pairs) = take_byte(i)?;
+            let (i, fields) = take_byte(i)?;
+            let (i, selection) = take_field_pairs(pgn, selection_pairs)(i)?;
+            let (i, fields) = count(take_byte, usize::from(fields))(i)?;
+            let read_fields = GroupFunction::ReadFields {
+                pgn,
+                proprietary,
+                unique_id,
+                selection,
+                fields,
+            };
+            Ok((i, read_fields))
+        }
+        4..=6 => {
+            let (i, (pgn, proprietary, unique_id, selection, values)) = parse_field_access(i)?;
+            let group_function = match function_code {
+                4 => GroupFunction::ReadFieldsReply {
+                    pgn,
+                    proprietary,
+                    unique_id,
+                    selection,
+                    values,
+                },
+                5 => GroupFunction::WriteFields {
+                    pgn,
+                    proprietary,
+                    unique_id,
+                    selection,
+                    values,
+                },
+                _ => GroupFunction::WriteFieldsReply {
+                    pgn,
+                    proprietary,
+                    unique_id,
+                    selection,
+                    values,
+                },
+            };
+            Ok((i, group_function))
+        }
+        _ => Err(nom::Err::Failure(nom::error::Error::new(
+            i,
+            nom::error::ErrorKind::Switch,
+        ))),
+    }
+}
+
+/// Keeps a message whose fields cannot be split up as it was sent.
+fn parse_unresolved(data: &[u8]) -> Result<GroupFunction, NmeaError> {
+    if data.len() < 4 {
+        return Err(NmeaError::ParseError);
+    }
+    Ok(GroupFunction::Unresolved {
+        function_code: data[0],
+        pgn: u32::from_le_bytes([data[1], data[2], data[3], 0]),
+        data: data[4..].to_vec(),
+    })
+}
+
+impl Message<GroupFunction, &[u8]> for GroupFunction {
+    fn get_data(data: &[u8]) -> Result<GroupFunction, NmeaError> {
+        let parse_result: IResult<&[u8], GroupFunction> =
+            nom::bits::bits(parse_group_function)(data);
+        match parse_result {
+            Ok((_, group_function)) => Ok(group_function),
+            Err(nom::Err::Failure(e)) if e.code == nom::error::ErrorKind::Verify => {
+                parse_unresolved(data)
+            }
+            Err(_e) => Err(NmeaError::ParseError),
+        }
+    }
+}
+
+fn put_field_pairs(writer: &mut FieldWriter, pairs: &[FieldPair]) {
+    for pair in pairs {
+        writer.put_u8(pair.field);
+        for byte in &pair.value.to_le_bytes()[..usize::from(pair.length).min(8)] {
+            writer.put_u8(*byte);
+        }
+    }
+}
+
+fn put_proprietary_id(writer: &mut FieldWriter, proprietary: Option<ProprietaryId>) {
+    if let Some(proprietary) = proprietary {
+        writer.put_u16(proprietary.to_raw());
+    }
+}
+
+impl Encode<Vec<u8>> for GroupFunction {
+    fn encode(&self) -> Vec<u8> {
+        let mut writer = FieldWriter::new();
+        match self {
+            GroupFunction::Request {
+                pgn,
+                transmission_interval,
+                transmission_interval_offset,
+                parameters,
+            } => {
+                writer.put_u8(0);
+                writer.put_u24(*pgn);
+                writer.put_u32(
+                    transmission_interval
+                        .map(|interval| interval.as_millis() as u32)
+                        .to_u32(),
+                );
+                writer.put_u16(
+                    transmission_interval_offset
+                        .map(|offset| (offset.as_millis() / 10) as u16)
+                        .to_u16(),
+                );
+                writer.put_u8(parameters.len() as u8);
+                put_field_pairs(&mut writer, parameters);
+            }
+            GroupFunction::Command {
+                pgn,
+                priority,
+                parameters,
+            } => {
+                writer.put_u8(1);
+                writer.put_u24(*pgn);
+                // Priority 8 means "leave unchanged", the upper nibble is
+                // reserved
+                writer.put_u8(priority.map_or(8, |priority| priority & 0x07) | 0xF0);
+                writer.put_u8(parameters.len() as u8);
+                put_field_pairs(&mut writer, parameters);
+            }
+            GroupFunction::Acknowledge {
+                pgn,
+                pgn_error,
+                transmission_interval_error,
+                parameter_errors,
+            } => {
+                writer.put_u8(2);
+                writer.put_u24(*pgn);
+                writer.put_u8(
+                    pgn_error.map_or(0x0F, |error| error as u8)
+                        | transmission_interval_error.map_or(0x0F, |error| error as u8) << 4,
+                );
+                writer.put_u8(parameter_errors.len() as u8);
+                for errors in parameter_errors.chunks(2) {
+                    let code = |error: Option<&Option<ParameterErrorCode>>| {
+                        error.map_or(0x0F, |error| error.map_or(0x0F, |error| error as u8))
+                    };
+                    writer.put_u8(code(errors.first()) | code(errors.get(1)) << 4);
+                }
+            }
+            GroupFunction::ReadFields {
+                pgn,
+                proprietary,
+                unique_id,
+                selection,
+                fields,
+            } => {
+                writer.put_u8(3);
+                writer.put_u24(*pgn);
+                put_proprietary_id(&mut writer, *proprietary);
+                writer.put_u8(*unique_id);
+                writer.put_u8(selection.len() as u8);
+                writer.put_u8(fields.len() as u8);
+                put_field_pairs(&mut writer, selection);
+                for field in fields {
+                    writer.put_u8(*field);
+                }
+            }
+            GroupFunction::ReadFieldsReply {
+                pgn,
+                proprietary,
+                unique_id,
+                selection,
+                values,
+            }
+            | GroupFunction::WriteFields {
+                pgn,
+                proprietary,
+                unique_id,
+                selection,
+                values,
+            }
+            | GroupFunction::WriteFieldsReply {
+                pgn,
+                proprietary,
+                unique_id,
+                selection,
+                values,
+            } => {
+                let function_code = match self {
+                    GroupFunction::ReadFieldsReply { .. } => 4,
+                    GroupFunction::WriteFields { .. } => 5,
+                    _ => 6,
+                };
+                writer.put_u8(function_code);
+                writer.put_u24(*pgn);
+                put_proprietary_id(&mut writer, *proprietary);
+                writer.put_u8(*unique_id);
+                writer.put_u8(selection.len() as u8);
+                writer.put_u8(values.len() as u8);
+                put_field_pairs(&mut writer, selection);
+                put_field_pairs(&mut writer, values);
+            }
+            GroupFunction::Unresolved {
+                function_code,
+                pgn,
+                data,
+            } => {
+                writer.put_u8(*function_code);
+                writer.put_u24(*pgn);
+                for byte in data {
+                    writer.put_u8(*byte);
+                }
+            }
+        }
+        writer.into_payload()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_instance_command() {
+        // Give the battery monitor reporting instance 0 instance 2
+        let command = GroupFunction::command_field(127508, 1, 2).unwrap();
+        let payload = command.encode();
+        assert_eq!(
+            vec![0x01, 0x14, 0xF2, 0x01, 0xF8, 0x01, 0x01, 0x02],
+            payload
+        );
+        assert_eq!(command, GroupFunction::get_data(&payload).unwrap());
+    }
+
+    #[test]
+    fn resolves_field_lengths() {
+        // Request temperature (130312) every 5 s, only for instance 3
+        let payload = [
+            0x00, 0x08, 0xFD, 0x01, 0x88, 0x13, 0x00, 0x00, 0xFF, 0xFF, 0x01, 0x02, 0x03,
+        ];
+        let request = GroupFunction::get_data(&payload).unwrap();
+        assert_eq!(
+            GroupFunction::Request {
+                pgn: 130312,
+                transmission_interval: FieldValue::Value(Duration::from_secs(5)),
+                transmission_interval_offset: FieldValue::NotAvailable,
+                parameters: vec![FieldPair {
+                    field: 2,
+                    value: 3,
+                    length: 1,
+                }],
+            },
+            request
+        );
+        assert_eq!(payload.to_vec(), request.encode());
+
+        let voltage = FieldPair::new(127508, 2, 0xFF38).unwrap();
+        assert_eq!(2, voltage.length);
+        assert_eq!(-200, voltage.get_signed_value());
+        assert!(FieldPair::new(127508, 9, 0).is_err());
+    }
+
+    #[test]
+    fn keeps_unknown_fields_undecoded() {
+        // A command for a PGN without a layout
+        let payload = [0x01, 0x00, 0xF0, 0x01, 0xF8, 0x01, 0x01, 0x00];
+        let command = GroupFunction::get_data(&payload).unwrap();
+        assert_eq!(
+            GroupFunction::Unresolved {
+                function_code: 1,
+                pgn: 126976,
+                data: vec![0xF8, 0x01, 0x01, 0x00],
+            },
+            command
+        );
+        assert_eq!(payload.to_vec(), command.encode());
+
+        // Battery status has no field 9
+        let payload = [0x01, 0x14, 0xF2, 0x01, 0xF8, 0x01, 0x09, 0x00];
+        assert!(matches!(
+            GroupFunction::get_data(&payload),
+            Ok(GroupFunction::Unresolved { pgn: 127508, .. })
+        ));
+        assert!(GroupFunction::get_data(&[0x01, 0x14, 0xF2]).is_err());
+    }
+
+    #[test]
+    fn sign_extends_any_length() {
+        let pair = |value: u64, length: u8| FieldPair {
+            field: 1,
+            value,
+            length,
+        };
+        assert_eq!(0, pair(0xFF, 0).get_signed_value());
+        assert_eq!(-1, pair(0xFF, 1).get_signed_value());
+        assert_eq!(-2, pair(u64::MAX - 1, 8).get_signed_value());
+        assert_eq!(-2, pair(u64::MAX - 1, 9).get_signed_value());
+    }
+
+    #[test]
+    fn resolves_lengths_of_decoded_pgns() {
+        // Set the instance of the inverter and the depth offset
+        assert_eq!(1, FieldPair::new(127509, 1, 0).unwrap().length);
+        let offset = FieldPair::new(128267, 3, 0xFE0C).unwrap();
+        assert_eq!(2, offset.length);
+        assert_eq!(-500, offset.get_signed_value());
+        assert_eq!(4, FieldPair::new(65029, 1, 0).unwrap().length);
+    }
+
+    #[test]
+    fn round_trips_acknowledge_and_write_fields() {
+        let acknowledge = GroupFunction::Acknowledge {
+            pgn: 127505,
+            pgn_error: Some(PgnErrorCode::Acknowledge),
+            transmission_interval_error: Some(TransmissionIntervalErrorCode::NotSupported),
+            parameter_errors: vec![
+                Some(ParameterErrorCode::Acknowledge),
+                Some(ParameterErrorCode::OutOfRange),
+                None,
+            ],
+        };
+        let payload = acknowledge.encode();
+        assert_eq!(
+            vec![0x02, 0x11, 0xF2, 0x01, 0x40, 0x03, 0x30, 0xFF],
+            payload
+        );
+        assert_eq!(acknowledge, GroupFunction::get_data(&payload).unwrap());
+
+        let write_fields = GroupFunction::WriteFields {
+            pgn: 65280,
+            proprietary: Some(ProprietaryId {
+                manufacturer_code: 1851,
+                industry_code: 4,
+            }),
+            unique_id: 7,
+            selection: Vec::new(),
+            values: Vec::new(),
+        };
+        let payload = write_fields.encode();
+        assert_eq!(
+            vec![0x05, 0x00, 0xFF, 0x00, 0x3B, 0x9F, 0x07, 0x00, 0x00],
+            payload
+        );
+        assert_eq!(write_fields, GroupFunction::get_data(&payload).unwrap());
+    }
+}
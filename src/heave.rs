@@ -0,0 +1,40 @@
+use core::time::Duration;
+
+use crate::{
+    field_value::FieldValue, nmea_frame::HeaveFrame, pgn_fields::HeaveFields, units::Distance,
+    Message, NmeaError,
+};
+
+/// PGN 127252
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Heave {
+    pub sid: FieldValue<u8>,
+    /// Vertical displacement, positive downwards.
+    pub heave: FieldValue<Distance>,
+    /// How long after the measurement the message was sent.
+    pub delay: FieldValue<Duration>,
+}
+
+impl Message<Heave, HeaveFrame> for Heave {
+    fn get_data(frame: HeaveFrame) -> Result<Heave, NmeaError> {
+        let fields = HeaveFields::get_data(&frame.data)?;
+        Ok(Heave {
+            sid: fields.sid.map(|sid| sid as u8),
+            heave: fields.heave.map(Distance::from_meters),
+            delay: fields.delay.map(Duration::from_secs_f64),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_heave() {
+        let data = [0, 0x9C, 0xFF, 0x05, 0x00, 0xFF, 0xFF, 0xFF];
+        let heave = Heave::get_data(HeaveFrame { data }).unwrap();
+        assert_eq!(-1.0, heave.heave.value().unwrap().get_meters());
+        assert_eq!(Duration::from_millis(50), heave.delay.value().unwrap());
+    }
+}
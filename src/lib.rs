@@ -1,25 +1,56 @@
 // #![cfg_attr(not(test), no_std)]
 
-use crate::nmea_frame::NmeaFrame;
+use crate::fast_packet::FastPacketAssembler;
+use crate::nmea_frame::{NmeaData, NmeaFrame};
 use bitfield::bitfield;
+pub mod ac_power;
+pub mod ais;
+pub mod ais_target;
+pub mod attitude;
 pub mod bearing_type;
 pub mod course_over_ground;
+pub mod cross_track_error;
 pub mod date;
+pub mod distance_log;
+pub mod electrical;
+pub mod engine_parameters;
+pub mod environmental;
+pub mod fast_packet;
+pub mod field_value;
+pub mod fluid_level;
+pub mod group_function;
+pub mod heave;
+pub mod magnetic_variation;
 pub mod navigation_data;
+pub mod network_management;
+pub mod network_map;
 pub mod nmea_frame;
 mod parse;
 pub mod pgn;
+pub mod pgn_fields;
+pub mod pgn_list;
+pub mod position_rapid_update;
+pub mod proprietary;
 pub mod rad;
+pub mod rate_of_turn;
+pub mod raymarine;
+pub mod route_information;
+pub mod speed_water_referenced;
+pub mod station_data;
+pub mod switch_bank;
+#[allow(dead_code)]
 mod system_time;
+pub mod transport_protocol;
+pub mod units;
 pub mod vessel_heading;
-use nmea_frame::{
-    COGSOGRapidUpdateFrame, NavigationDataFrame, SystemTimeFrame, VesselHeadingFrame,
-};
+pub mod water_depth;
+mod write;
 use num_traits::FromPrimitive;
-use pgn::PGN;
+use pgn::{PgnInfo, PgnKind, PGN};
 
 pub trait FastPacketMessage<T, S> {
     fn get_data(&mut self) -> Result<(), NmeaError>;
+    #[allow(clippy::result_unit_err)]
     fn parse_frame(&mut self, data: T) -> Result<(), ()>;
 }
 
@@ -27,6 +58,10 @@ pub trait Message<T, S> {
     fn get_data(data: S) -> Result<T, NmeaError>;
 }
 
+pub trait Encode<S> {
+    fn encode(&self) -> S;
+}
+
 bitfield! {
     pub struct NmeaId(u32);
     impl Debug;
@@ -48,6 +83,13 @@ pub enum NmeaError {
     ParseError,
     NotImplemented,
     NotFullyParsed,
+    /// The data does not fit in the message it was given for.
+    InvalidLength,
+    /// A transfer to the same destination is still in progress.
+    Busy,
+    /// The PGN only arrives through the ISO transport protocol, see
+    /// `transport_protocol::TransportProtocol`.
+    TransportProtocol,
 }
 
 impl NmeaId {
@@ -61,12 +103,8 @@ impl NmeaId {
 
     pub fn get_raw_pgn(&self) -> u32 {
         match self.get_format() {
-            NmeaPgnFormat::PDU1 => {
-                ((self.reserved_data_page() as u32) << 16) | ((self.pf() as u32) << 8)
-            }
-            NmeaPgnFormat::PDU2 => {
-                ((self.reserved_data_page() as u32) << 16 | (self.pf() as u32) << 8) | self.ps()
-            }
+            NmeaPgnFormat::PDU1 => (self.reserved_data_page() << 16) | (self.pf() << 8),
+            NmeaPgnFormat::PDU2 => (self.reserved_data_page() << 16 | self.pf() << 8) | self.ps(),
         }
     }
 
@@ -76,29 +114,47 @@ impl NmeaId {
         FromPrimitive::from_u32(pgn)
     }
 
-    pub fn parse_data(&self, data: &[u8]) -> Result<NmeaFrame, NmeaError> {
+    pub fn is_proprietary(&self) -> bool {
+        pgn::is_proprietary(self.get_raw_pgn())
+    }
+
+    pub fn get_pgn_info(&self) -> PgnInfo {
+        PgnInfo::from_raw(self.get_raw_pgn())
+    }
+
+    /// Turns the data of one CAN frame into the data of its PGN. Fast packet
+    /// frames are collected in `fast_packets` and give `None` until the last
+    /// frame of their message arrives. Messages sent with the ISO transport
+    /// protocol are put back together by
+    /// `transport_protocol::TransportProtocol` instead, so their PGNs fail
+    /// with `NmeaError::TransportProtocol`.
+    pub fn parse_data(
+        &self,
+        data: &[u8],
+        fast_packets: &mut FastPacketAssembler,
+    ) -> Result<Option<NmeaData>, NmeaError> {
         let pgn = self.get_pgn();
+        let raw_pgn = self.get_raw_pgn();
 
-        match pgn {
-            Some(PGN::SystemTime) => {
-                let data: [u8; 8] = data[0..8].try_into().unwrap();
-                Ok(NmeaFrame::SystemTime(SystemTimeFrame { data }))
-            }
-            Some(PGN::VesselHeading) => {
-                let data: [u8; 8] = data[0..8].try_into().unwrap();
-                Ok(NmeaFrame::VesselHeading(VesselHeadingFrame { data }))
-            }
-            Some(PGN::NavigationData) => {
-                let data: [u8; 8] = data[0..8].try_into().unwrap();
-                Ok(NmeaFrame::NavigationData(NavigationDataFrame { data }))
-            }
-            Some(PGN::COGSOGRapidUpdate) => {
-                let data: [u8; 8] = data[0..8].try_into().unwrap();
-                Ok(NmeaFrame::COGSOGRapidUpdate(COGSOGRapidUpdateFrame {
-                    data,
-                }))
-            }
-            _ => Err(NmeaError::NotImplemented),
+        // Single frames may be sent with a shorter DLC than 8, fast packet
+        // frames only at the end of a message. Both are padded with 0xFF
+        // which reads as "not available".
+        if data.is_empty() || data.len() > 8 {
+            return Err(NmeaError::ParseError);
+        }
+        let mut frame = [0xFF; 8];
+        frame[..data.len()].copy_from_slice(data);
+
+        match PgnInfo::from_raw(raw_pgn).kind {
+            PgnKind::Single => Ok(Some(match pgn.and_then(|pgn| NmeaFrame::new(pgn, frame)) {
+                Some(nmea_frame) => NmeaData::Frame(nmea_frame),
+                None => NmeaData::Unknown(raw_pgn, frame.to_vec()),
+            })),
+            PgnKind::FastPacket => Ok(fast_packets.push(self, &frame).map(|payload| match pgn {
+                Some(pgn) => NmeaData::FastPacket(pgn, payload),
+                None => NmeaData::Unknown(raw_pgn, payload),
+            })),
+            PgnKind::TransportProtocol => Err(NmeaError::TransportProtocol),
         }
     }
 }
@@ -124,4 +180,91 @@ mod tests {
         let nmea_id = NmeaId(id);
         assert_eq!(nmea_id.get_pgn(), Some(PGN::VesselHeading));
     }
+
+    #[test]
+    fn pads_short_single_frame() {
+        let nmea_id = NmeaId(0b01001111100010001001000000000);
+        let mut fast_packets = FastPacketAssembler::new();
+        match nmea_id.parse_data(&[159, 255, 255], &mut fast_packets) {
+            Ok(Some(NmeaData::Frame(NmeaFrame::VesselHeading(frame)))) => {
+                assert_eq!([159, 255, 255, 255, 255, 255, 255, 255], frame.data)
+            }
+            _ => panic!("expected a vessel heading frame"),
+        }
+    }
+
+    #[test]
+    fn reassembles_fast_packet_frames() {
+        // Navigation data, PGN 129284, from source 1
+        let nmea_id = NmeaId(0x0DF90401);
+        let frames: [&[u8]; 5] = [
+            &[64, 34, 204, 55, 29, 16, 0, 0],
+            &[65, 48, 87, 21, 39, 170, 76, 255],
+            &[66, 255, 255, 255, 255, 255, 255, 255],
+            &[67, 0, 0, 0, 0, 255, 255, 255],
+            &[68, 127, 255, 255, 255, 127, 141, 0],
+        ];
+        let mut fast_packets = FastPacketAssembler::new();
+        for frame in &frames[..4] {
+            assert!(nmea_id
+                .parse_data(frame, &mut fast_packets)
+                .unwrap()
+                .is_none());
+        }
+        match nmea_id.parse_data(frames[4], &mut fast_packets) {
+            Ok(Some(NmeaData::FastPacket(PGN::NavigationData, payload))) => {
+                assert_eq!(34, payload.len());
+                assert_eq!([204, 55, 29, 16, 0, 0], payload[..6]);
+                assert_eq!([141, 0], payload[32..]);
+                let navigation_data =
+                    navigation_data::NavigationData::from_payload(&payload).unwrap();
+                assert_eq!(
+                    1.41,
+                    navigation_data
+                        .waypoint_closing_velocity
+                        .value()
+                        .unwrap()
+                        .get_meters_per_second()
+                );
+            }
+            _ => panic!("expected a navigation data payload"),
+        }
+    }
+
+    #[test]
+    fn passes_on_unknown_pgns() {
+        let mut fast_packets = FastPacketAssembler::new();
+
+        // Proprietary single frame PGN 65280 from source 1
+        let nmea_id = NmeaId(0x18FF0001);
+        match nmea_id.parse_data(&[0x3B, 0x9F, 1, 2], &mut fast_packets) {
+            Ok(Some(NmeaData::Unknown(65280, data))) => {
+                assert_eq!(vec![0x3B, 0x9F, 1, 2, 255, 255, 255, 255], data)
+            }
+            _ => panic!("expected an unknown single frame"),
+        }
+
+        // Proprietary fast packet PGN 130820 from source 1
+        let nmea_id = NmeaId(0x19FF0401);
+        let payload: Vec<u8> = (0..10).collect();
+        let frames = fast_packet::encode(2, &payload).unwrap();
+        assert!(nmea_id
+            .parse_data(&frames[0], &mut fast_packets)
+            .unwrap()
+            .is_none());
+        match nmea_id.parse_data(&frames[1], &mut fast_packets) {
+            Ok(Some(NmeaData::Unknown(130820, data))) => assert_eq!(payload, data),
+            _ => panic!("expected an unknown fast packet payload"),
+        }
+    }
+
+    #[test]
+    fn leaves_transport_protocol_pgns_to_the_session_manager() {
+        // ISO Commanded Address, PGN 65240, from source 1
+        let nmea_id = NmeaId(0x18FED801);
+        assert!(matches!(
+            nmea_id.parse_data(&[0; 8], &mut FastPacketAssembler::new()),
+            Err(NmeaError::TransportProtocol)
+        ));
+    }
 }
@@ -0,0 +1,83 @@
+use core::f32::consts::TAU;
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use crate::{
+    date::Date, field_value::FieldValue, nmea_frame::MagneticVariationFrame,
+    pgn_fields::MagneticVariationFields, rad::Rad, Message, NmeaError,
+};
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum MagneticVariationSource {
+    Manual = 0,
+    AutomaticChart = 1,
+    AutomaticTable = 2,
+    AutomaticCalculation = 3,
+    WMM2000 = 4,
+    WMM2005 = 5,
+    WMM2010 = 6,
+    WMM2015 = 7,
+    WMM2020 = 8,
+}
+
+/// PGN 127258
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct MagneticVariation {
+    pub sid: FieldValue<u8>,
+    pub source: Option<MagneticVariationSource>,
+    /// Date the variation model is valid from.
+    pub age_of_service: FieldValue<Date>,
+    /// Positive when magnetic north is east of true north.
+    pub variation: FieldValue<Rad>,
+}
+
+impl MagneticVariation {
+    /// Converts a magnetic heading into a true heading.
+    pub fn get_true_heading(&self, magnetic_heading: Rad) -> Option<Rad> {
+        let variation = self.variation.value()?;
+        let heading = (magnetic_heading.get_radians() + variation.get_radians()).rem_euclid(TAU);
+        Some(Rad::from_radians(heading))
+    }
+}
+
+impl Message<MagneticVariation, MagneticVariationFrame> for MagneticVariation {
+    fn get_data(frame: MagneticVariationFrame) -> Result<MagneticVariation, NmeaError> {
+        let fields = MagneticVariationFields::get_data(&frame.data)?;
+        Ok(MagneticVariation {
+            sid: fields.sid.map(|sid| sid as u8),
+            source: fields.source.value().and_then(FromPrimitive::from_f64),
+            age_of_service: fields.age_of_service.map(|days| Date::new(days as u16)),
+            variation: fields
+                .variation
+                .map(|variation| Rad::from_radians(variation as f32)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_magnetic_variation() {
+        let data = [0, 0xF8, 0x80, 0x4C, 0xB9, 0x06, 0xFF, 0xFF];
+        let magnetic_variation =
+            MagneticVariation::get_data(MagneticVariationFrame { data }).unwrap();
+        assert_eq!(
+            Some(MagneticVariationSource::WMM2020),
+            magnetic_variation.source
+        );
+        assert_eq!(
+            0.1721,
+            magnetic_variation.variation.value().unwrap().get_radians()
+        );
+        let true_heading = magnetic_variation
+            .get_true_heading(Rad::from_radians(6.2))
+            .unwrap();
+        assert_eq!(
+            0.0889,
+            (true_heading.get_radians() * 10000.0).round() / 10000.0
+        );
+    }
+}
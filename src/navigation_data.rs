@@ -1,14 +1,17 @@
 use crate::{
     bearing_type::BearingType,
+    date::{Date, DateTime, Time},
+    field_value::FieldValue,
     nmea_frame::NavigationDataFrame,
-    parse::{self, take_five_bits, take_three_bits, take_two_bits},
+    parse::{take_five_bits, take_three_bits, BitInput},
+    pgn_fields::NavigationDataFields,
     rad::Rad,
+    units::{Distance, Latitude, Longitude, Speed},
     vessel_heading::DirectionReference,
-    FastPacketMessage, NmeaError,
+    FastPacketMessage, Message, NmeaError,
 };
-use nom::{AsBytes, IResult};
+use nom::IResult;
 use num_traits::FromPrimitive;
-use parse::{take_byte, take_u16, take_u32, BitInput};
 
 fn parse_frame_and_sequence(i: BitInput) -> IResult<BitInput, (u8, u8)> {
     let (i, sequence) = take_three_bits(i)?;
@@ -16,70 +19,6 @@ fn parse_frame_and_sequence(i: BitInput) -> IResult<BitInput, (u8, u8)> {
     Ok((i, (sequence, frame_count)))
 }
 
-fn parse_navigation_data(
-    i: BitInput,
-) -> IResult<
-    BitInput,
-    (
-        u8,
-        u32,
-        Option<DirectionReference>,
-        bool,
-        bool,
-        Option<BearingType>,
-        u32,
-        u16,
-        u16,
-        u16,
-        u32,
-        u32,
-        u32,
-        u16,
-        u32,
-    ),
-> {
-    let (i, sid) = take_byte(i)?;
-    let (i, distance_to_waypoint) = take_u32(i)?;
-    let (i, course_bearing_reference_raw) = take_two_bits(i)?;
-    let (i, perpindicular_crossed) = take_two_bits(i)?;
-    let (i, arrival_circle_entered) = take_two_bits(i)?;
-    let (i, calculation_type) = take_two_bits(i)?;
-    let (i, eta_time) = take_u32(i)?;
-    let (i, eta_date) = take_u16(i)?;
-    let (i, bearing_origin_to_destination_waypoint) = take_u16(i)?;
-    let (i, bearing_origin_to_waypoint) = take_u16(i)?;
-    let (i, origin_waypoint_number) = take_u32(i)?;
-    let (i, destination_waypoint_number) = take_u32(i)?;
-    let (i, destination_latitude) = take_u32(i)?;
-    let (i, destination_longitude) = take_u32(i)?;
-    let (i, waypoint_closing_velocity) = take_u16(i)?;
-
-    let course_bearing_reference: Option<DirectionReference> =
-        FromPrimitive::from_u8(course_bearing_reference_raw);
-    let calculation_type: Option<BearingType> = FromPrimitive::from_u8(calculation_type);
-
-    Ok((
-        i,
-        (
-            sid,
-            distance_to_waypoint,
-            course_bearing_reference,
-            perpindicular_crossed == 1,
-            arrival_circle_entered == 1,
-            calculation_type,
-            eta_time,
-            eta_date,
-            bearing_origin_to_waypoint,
-            bearing_origin_to_destination_waypoint,
-            origin_waypoint_number,
-            destination_latitude,
-            destination_longitude,
-            waypoint_closing_velocity,
-            destination_waypoint_number,
-        ),
-    ))
-}
-
 impl FastPacketMessage<NavigationDataFrame, [u8; 64]> for NavigationData {
     fn parse_frame(&mut self, frame: NavigationDataFrame) -> Result<(), ()> {
         let data = frame.data;
@@ -91,16 +30,12 @@ impl FastPacketMessage<NavigationDataFrame, [u8; 64]> for NavigationData {
             0 => {
                 println!("start frame");
                 self.start_sequence = sequence;
-                for index in 0..6 {
-                    self.data[index] = i[index + 1];
-                }
+                self.data[..6].copy_from_slice(&i[1..7]);
             }
             _ => {
                 if self.start_sequence == sequence {
-                    for index in 0..7 {
-                        let byte_to_set: usize = index + (6 + ((frame_count - 1) * 7)) as usize;
-                        self.data[byte_to_set] = i[index];
-                    }
+                    let offset = (6 + ((frame_count - 1) * 7)) as usize;
+                    self.data[offset..offset + 7].copy_from_slice(&i[..7]);
                 }
             }
         }
@@ -115,20 +50,6 @@ impl FastPacketMessage<NavigationDataFrame, [u8; 64]> for NavigationData {
     }
 }
 
-#[derive(Debug)]
-pub struct Time {
-    time: u32,
-}
-
-impl Time {
-    pub fn new(time: u32) -> Time {
-        Time { time }
-    }
-    pub fn get_seconds(&self) -> u32 {
-        self.time / 10000
-    }
-}
-
 #[derive(Debug)]
 pub struct NavigationData {
     data: [u8; 64],
@@ -136,89 +57,73 @@ pub struct NavigationData {
     end_sequence: u8,
     pub frame: u8,
     pub length: Option<u8>,
-    pub sid: Option<u8>,
-    pub distance_to_waypoint: Option<u32>,
+    pub sid: FieldValue<u8>,
+    pub distance_to_waypoint: FieldValue<Distance>,
     pub course_bearing_reference: Option<DirectionReference>,
-    pub perpindicular_crossed: Option<bool>,
-    pub arrival_circle_entered: Option<bool>,
+    pub perpindicular_crossed: FieldValue<bool>,
+    pub arrival_circle_entered: FieldValue<bool>,
     pub calculation_type: Option<BearingType>,
-    pub eta_time: Option<Time>,
-    pub eta_date: Option<u16>,
-    pub bearing_origin_to_destination_waypoint: Option<Rad>,
-    pub bearing_origin_to_waypoint: Option<Rad>,
-    pub origin_waypoint_number: Option<u32>,
-    pub destination_waypoint_number: Option<u32>,
-    pub destination_latitude: Option<u32>,
-    pub destination_longitude: Option<u32>,
-    pub waypoint_closing_velocity: Option<f32>,
+    pub eta_time: FieldValue<Time>,
+    pub eta_date: FieldValue<Date>,
+    pub bearing_origin_to_destination_waypoint: FieldValue<Rad>,
+    pub bearing_origin_to_waypoint: FieldValue<Rad>,
+    pub origin_waypoint_number: FieldValue<u32>,
+    pub destination_waypoint_number: FieldValue<u32>,
+    pub destination_latitude: FieldValue<Latitude>,
+    pub destination_longitude: FieldValue<Longitude>,
+    pub waypoint_closing_velocity: FieldValue<Speed>,
 }
 
 impl NavigationData {
     pub fn parse_navigation_data(&mut self) -> Result<(), NmeaError> {
-        let parse_result: IResult<
-            &[u8],
-            (
-                u8,
-                u32,
-                Option<DirectionReference>,
-                bool,
-                bool,
-                Option<BearingType>,
-                u32,
-                u16,
-                u16,
-                u16,
-                u32,
-                u32,
-                u32,
-                u16,
-                u32,
-            ),
-        > = nom::bits::bits(parse_navigation_data)(self.data.as_bytes());
-
-        if let Err(_) = parse_result {
-            return Err(NmeaError::ParseError);
-        }
-
-        let (
-            _,
-            (
-                sid,
-                distance_to_waypoint,
-                course_bearing_reference,
-                perpindicular_crossed,
-                arrival_circle_entered,
-                calculation_type,
-                eta_time,
-                eta_date,
-                bearing_origin_to_waypoint,
-                bearing_origin_to_destination_waypoint,
-                origin_waypoint_number,
-                destination_latitude,
-                destination_longitude,
-                waypoint_closing_velocity,
-                destination_waypoint_number,
-            ),
-        ) = parse_result.unwrap();
-
-        self.sid = Some(sid);
-        self.distance_to_waypoint = Some(distance_to_waypoint);
-        self.course_bearing_reference = course_bearing_reference;
-        self.perpindicular_crossed = Some(perpindicular_crossed);
-        self.arrival_circle_entered = Some(arrival_circle_entered);
-        self.calculation_type = calculation_type;
-        self.eta_time = Some(Time::new(eta_time));
-        self.eta_date = Some(eta_date);
-        self.bearing_origin_to_waypoint = Some(Rad::new(bearing_origin_to_waypoint));
+        let fields = NavigationDataFields::get_data(&self.data)?;
+        let angle =
+            |radians: FieldValue<f64>| radians.map(|radians| Rad::from_radians(radians as f32));
+
+        self.sid = fields.sid.map(|sid| sid as u8);
+        self.distance_to_waypoint = fields.distance_to_waypoint.map(Distance::from_meters);
+        self.course_bearing_reference =
+            DirectionReference::from_field(fields.course_bearing_reference);
+        self.perpindicular_crossed = fields.perpendicular_crossed.to_status();
+        self.arrival_circle_entered = fields.arrival_circle_entered.to_status();
+        self.calculation_type = fields
+            .calculation_type
+            .value()
+            .and_then(FromPrimitive::from_f64);
+        self.eta_time = fields.eta_time.map(Time::from_seconds);
+        self.eta_date = fields.eta_date.map(|days| Date::new(days as u16));
+        self.bearing_origin_to_waypoint = angle(fields.bearing_position_to_destination_waypoint);
         self.bearing_origin_to_destination_waypoint =
-            Some(Rad::new(bearing_origin_to_destination_waypoint));
-        self.origin_waypoint_number = Some(origin_waypoint_number);
-        self.destination_latitude = Some(destination_latitude);
-        self.destination_longitude = Some(destination_longitude);
-        self.waypoint_closing_velocity = Some(waypoint_closing_velocity as f32 * 0.01);
-        self.destination_waypoint_number = Some(destination_waypoint_number);
+            angle(fields.bearing_origin_to_destination_waypoint);
+        self.origin_waypoint_number = fields.origin_waypoint_number.map(|number| number as u32);
+        self.destination_latitude = fields.destination_latitude.map(Latitude::from_degrees);
+        self.destination_longitude = fields.destination_longitude.map(Longitude::from_degrees);
+        self.waypoint_closing_velocity = fields
+            .waypoint_closing_velocity
+            .map(|velocity| Speed::from_meters_per_second(velocity as f32));
+        self.destination_waypoint_number = fields
+            .destination_waypoint_number
+            .map(|number| number as u32);
         Ok(())
     }
+
+    /// Decodes a payload already put back together, as returned by
+    /// `NmeaId::parse_data`.
+    pub fn from_payload(payload: &[u8]) -> Result<NavigationData, NmeaError> {
+        let mut navigation_data = NavigationData::new();
+        let length = payload.len().min(navigation_data.data.len());
+        navigation_data.data[..length].copy_from_slice(&payload[..length]);
+        navigation_data.parse_navigation_data()?;
+        Ok(navigation_data)
+    }
+
+    pub fn get_eta(&self) -> Option<DateTime> {
+        Some(DateTime {
+            date: self.eta_date.value()?,
+            time: self.eta_time.value()?,
+        })
+    }
+
     pub fn new() -> Self {
         NavigationData {
             data: [0; 64],
@@ -226,28 +131,35 @@ impl NavigationData {
             start_sequence: 0,
             end_sequence: 0,
             frame: 0,
-            sid: None,
-            distance_to_waypoint: None,
-            perpindicular_crossed: None,
-            arrival_circle_entered: None,
+            sid: FieldValue::NotAvailable,
+            distance_to_waypoint: FieldValue::NotAvailable,
+            perpindicular_crossed: FieldValue::NotAvailable,
+            arrival_circle_entered: FieldValue::NotAvailable,
             calculation_type: None,
             course_bearing_reference: None,
-            eta_time: None,
-            eta_date: None,
-            bearing_origin_to_waypoint: None,
-            bearing_origin_to_destination_waypoint: None,
-            origin_waypoint_number: None,
-            destination_latitude: None,
-            destination_longitude: None,
-            waypoint_closing_velocity: None,
-            destination_waypoint_number: None,
+            eta_time: FieldValue::NotAvailable,
+            eta_date: FieldValue::NotAvailable,
+            bearing_origin_to_waypoint: FieldValue::NotAvailable,
+            bearing_origin_to_destination_waypoint: FieldValue::NotAvailable,
+            origin_waypoint_number: FieldValue::NotAvailable,
+            destination_latitude: FieldValue::NotAvailable,
+            destination_longitude: FieldValue::NotAvailable,
+            waypoint_closing_velocity: FieldValue::NotAvailable,
+            destination_waypoint_number: FieldValue::NotAvailable,
         }
     }
 }
 
+impl Default for NavigationData {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use nom::AsBytes;
 
     #[test]
     fn parses_navigation_data_correctly_2() {
@@ -264,18 +176,22 @@ mod tests {
         for data in nav_data {
             let bytes = data.as_bytes();
             let data: [u8; 8] = bytes[0..8].try_into().unwrap();
-            let _ = navigation_data
+            navigation_data
                 .parse_frame(NavigationDataFrame { data })
                 .unwrap();
         }
         let _ = navigation_data.parse_navigation_data();
-        assert_eq!(1.41, navigation_data.waypoint_closing_velocity.unwrap());
         assert_eq!(
-            6.5534997,
+            1.41,
             navigation_data
-                .bearing_origin_to_waypoint
+                .waypoint_closing_velocity
+                .value()
                 .unwrap()
-                .get_radians()
+                .get_meters_per_second()
+        );
+        assert_eq!(
+            FieldValue::NotAvailable,
+            navigation_data.bearing_origin_to_waypoint
         );
     }
 }
@@ -0,0 +1,442 @@
+use core::time::Duration;
+
+use bitfield::bitfield;
+use nom::{sequence::pair, IResult};
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use crate::{
+    field_value::FieldValue,
+    nmea_frame::{HeartbeatFrame, ISOAcknowledgementFrame, ISOAddressClaimFrame, ISORequestFrame},
+    parse::{parse_at, take_byte, take_fixed_string, take_lau_string, take_u24, BitInput},
+    pgn::PGN,
+    pgn_fields::{HeartbeatFields, ProductInformationFields},
+    units::Current,
+    write::FieldWriter,
+    Encode, Message, NmeaError,
+};
+
+/// PGN 59904, asks one device or the whole bus (destination 255) to send a
+/// PGN.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ISORequest {
+    pub pgn: u32,
+}
+
+impl ISORequest {
+    /// The requested PGN, if this crate knows it.
+    pub fn get_pgn(&self) -> Option<PGN> {
+        FromPrimitive::from_u32(self.pgn)
+    }
+}
+
+fn parse_iso_request(i: BitInput) -> IResult<BitInput, ISORequest> {
+    let (i, pgn) = take_u24(i)?;
+    Ok((i, ISORequest { pgn }))
+}
+
+impl Message<ISORequest, ISORequestFrame> for ISORequest {
+    fn get_data(frame: ISORequestFrame) -> Result<ISORequest, NmeaError> {
+        let data = frame.data;
+        let parse_result: IResult<&[u8], ISORequest> = nom::bits::bits(parse_iso_request)(&data);
+        match parse_result {
+            Ok((_, request)) => Ok(request),
+            Err(_e) => Err(NmeaError::ParseError),
+        }
+    }
+}
+
+impl Encode<ISORequestFrame> for ISORequest {
+    fn encode(&self) -> ISORequestFrame {
+        let mut writer = FieldWriter::new();
+        writer.put_u24(self.pgn);
+        ISORequestFrame {
+            data: writer.into_frame(),
+        }
+    }
+}
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum AcknowledgementControl {
+    Ack = 0,
+    Nak = 1,
+    AccessDenied = 2,
+    AddressBusy = 3,
+}
+
+/// PGN 59392
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ISOAcknowledgement {
+    pub control: Option<AcknowledgementControl>,
+    pub group_function: FieldValue<u8>,
+    pub pgn: u32,
+}
+
+impl ISOAcknowledgement {
+    /// The answer to a request for a PGN this device does not send.
+    pub fn nak(pgn: u32) -> Self {
+        ISOAcknowledgement {
+            control: Some(AcknowledgementControl::Nak),
+            group_function: FieldValue::NotAvailable,
+            pgn,
+        }
+    }
+}
+
+fn parse_iso_acknowledgement(i: BitInput) -> IResult<BitInput, ISOAcknowledgement> {
+    let (i, control) = take_byte(i)?;
+    let (i, group_function) = take_byte(i)?;
+    let (i, _) = take_u24(i)?;
+    let (i, pgn) = take_u24(i)?;
+
+    let acknowledgement = ISOAcknowledgement {
+        control: FromPrimitive::from_u8(control),
+        group_function: FieldValue::from_u8(group_function),
+        pgn,
+    };
+    Ok((i, acknowledgement))
+}
+
+impl Message<ISOAcknowledgement, ISOAcknowledgementFrame> for ISOAcknowledgement {
+    fn get_data(frame: ISOAcknowledgementFrame) -> Result<ISOAcknowledgement, NmeaError> {
+        let data = frame.data;
+        let parse_result: IResult<&[u8], ISOAcknowledgement> =
+            nom::bits::bits(parse_iso_acknowledgement)(&data);
+        match parse_result {
+            Ok((_, acknowledgement)) => Ok(acknowledgement),
+            Err(_e) => Err(NmeaError::ParseError),
+        }
+    }
+}
+
+impl Encode<ISOAcknowledgementFrame> for ISOAcknowledgement {
+    fn encode(&self) -> ISOAcknowledgementFrame {
+        let mut writer = FieldWriter::new();
+        writer.put_u8(self.control.map_or(0xFF, |control| control as u8));
+        writer.put_u8(self.group_function.to_u8());
+        writer.put_u24(0xFFFFFF);
+        writer.put_u24(self.pgn);
+        ISOAcknowledgementFrame {
+            data: writer.into_frame(),
+        }
+    }
+}
+
+bitfield! {
+    /// The 64 bit NAME a device claims an address with. It stays the same
+    /// when the device has to move to another address.
+    #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct ISOName(u64);
+    impl Debug;
+    pub u32, unique_number, set_unique_number: 20, 0;
+    pub u16, manufacturer_code, set_manufacturer_code: 31, 21;
+    pub u8, device_instance, set_device_instance: 39, 32;
+    pub u8, device_function, set_device_function: 47, 40;
+    pub u8, device_class, set_device_class: 55, 49;
+    pub u8, system_instance, set_system_instance: 59, 56;
+    pub u8, industry_group, set_industry_group: 62, 60;
+    pub arbitrary_address_capable, set_arbitrary_address_capable: 63;
+}
+
+/// PGN 60928
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ISOAddressClaim {
+    pub name: ISOName,
+}
+
+impl Message<ISOAddressClaim, ISOAddressClaimFrame> for ISOAddressClaim {
+    fn get_data(frame: ISOAddressClaimFrame) -> Result<ISOAddressClaim, NmeaError> {
+        Ok(ISOAddressClaim {
+            name: ISOName(u64::from_le_bytes(frame.data)),
+        })
+    }
+}
+
+impl Encode<ISOAddressClaimFrame> for ISOAddressClaim {
+    fn encode(&self) -> ISOAddressClaimFrame {
+        ISOAddressClaimFrame {
+            data: self.name.0.to_le_bytes(),
+        }
+    }
+}
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum ControllerState {
+    ErrorActive = 0,
+    ErrorPassive = 1,
+    BusOff = 2,
+}
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum EquipmentStatus {
+    Operational = 0,
+    Fault = 1,
+}
+
+/// PGN 126993
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Heartbeat {
+    /// How often the heartbeat is sent.
+    pub interval: FieldValue<Duration>,
+    /// Counts up to 252 and wraps around.
+    pub sequence_counter: u8,
+    pub controller1_state: Option<ControllerState>,
+    pub controller2_state: Option<ControllerState>,
+    pub equipment_status: Option<EquipmentStatus>,
+}
+
+impl Message<Heartbeat, HeartbeatFrame> for Heartbeat {
+    fn get_data(frame: HeartbeatFrame) -> Result<Heartbeat, NmeaError> {
+        let fields = HeartbeatFields::get_data(&frame.data)?;
+        Ok(Heartbeat {
+            interval: fields
+                .data_transmit_offset
+                .map(|seconds| Duration::from_millis((seconds * 1000.0).round() as u64)),
+            sequence_counter: fields
+                .sequence_counter
+                .value()
+                .map_or(u8::MAX, |counter| counter as u8),
+            controller1_state: fields
+                .controller1_state
+                .value()
+                .and_then(FromPrimitive::from_f64),
+            controller2_state: fields
+                .controller2_state
+                .value()
+                .and_then(FromPrimitive::from_f64),
+            equipment_status: fields
+                .equipment_status
+                .value()
+                .and_then(FromPrimitive::from_f64),
+        })
+    }
+}
+
+impl Encode<HeartbeatFrame> for Heartbeat {
+    fn encode(&self) -> HeartbeatFrame {
+        let state = |state: Option<ControllerState>| {
+            state.map_or(FieldValue::NotAvailable, |state| {
+                FieldValue::Value(f64::from(state as u8))
+            })
+        };
+        HeartbeatFields {
+            data_transmit_offset: self.interval.map(|interval| interval.as_secs_f64()),
+            sequence_counter: FieldValue::Value(f64::from(self.sequence_counter)),
+            controller1_state: state(self.controller1_state),
+            controller2_state: state(self.controller2_state),
+            equipment_status: self
+                .equipment_status
+                .map_or(FieldValue::NotAvailable, |status| {
+                    FieldValue::Value(f64::from(status as u8))
+                }),
+        }
+        .to_frame()
+    }
+}
+
+/// PGN 126996, a fast packet message.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ProductInformation {
+    /// Version of the standard the device was certified against, times 1000.
+    pub nmea_2000_version: FieldValue<u16>,
+    pub product_code: FieldValue<u16>,
+    pub model_id: String,
+    pub software_version: String,
+    pub model_version: String,
+    pub model_serial_code: String,
+    pub certification_level: FieldValue<u8>,
+    /// Load equivalency number, the bus current drawn in units of 50 mA.
+    pub load_equivalency: FieldValue<u8>,
+}
+
+impl ProductInformation {
+    pub fn get_load_current(&self) -> Option<Current> {
+        let load_equivalency = self.load_equivalency.value()?;
+        Some(Current::from_amperes(f32::from(load_equivalency) * 0.05))
+    }
+}
+
+impl Message<ProductInformation, &[u8]> for ProductInformation {
+    fn get_data(data: &[u8]) -> Result<ProductInformation, NmeaError> {
+        let fields = ProductInformationFields::get_data(data)?;
+        let ((model_id, software_version), (model_version, model_serial_code)) = parse_at(
+            data,
+            4,
+            pair(
+                pair(take_fixed_string(32), take_fixed_string(32)),
+                pair(take_fixed_string(32), take_fixed_string(32)),
+            ),
+        )?;
+        Ok(ProductInformation {
+            nmea_2000_version: fields.nmea2000_version.map(|version| version as u16),
+            product_code: fields.product_code.map(|code| code as u16),
+            model_id,
+            software_version,
+            model_version,
+            model_serial_code,
+            certification_level: fields.certification_level.map(|level| level as u8),
+            load_equivalency: fields.load_equivalency.map(|load| load as u8),
+        })
+    }
+}
+
+impl Encode<Vec<u8>> for ProductInformation {
+    fn encode(&self) -> Vec<u8> {
+        let mut payload = ProductInformationFields {
+            nmea2000_version: self.nmea_2000_version.map(f64::from),
+            product_code: self.product_code.map(f64::from),
+            certification_level: self.certification_level.map(f64::from),
+            load_equivalency: self.load_equivalency.map(f64::from),
+        }
+        .encode();
+        let mut writer = FieldWriter::new();
+        writer.put_fixed_string(&self.model_id, 32);
+        writer.put_fixed_string(&self.software_version, 32);
+        writer.put_fixed_string(&self.model_version, 32);
+        writer.put_fixed_string(&self.model_serial_code, 32);
+        payload[4..132].copy_from_slice(&writer.into_payload());
+        payload
+    }
+}
+
+/// PGN 126998, a fast packet message.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConfigurationInformation {
+    pub installation_description1: String,
+    pub installation_description2: String,
+    pub manufacturer_information: String,
+}
+
+fn parse_configuration_information(i: BitInput) -> IResult<BitInput, ConfigurationInformation> {
+    let (i, installation_description1) = take_lau_string(i)?;
+    let (i, installation_description2) = take_lau_string(i)?;
+    let (i, manufacturer_information) = take_lau_string(i)?;
+
+    let configuration_information = ConfigurationInformation {
+        installation_description1,
+        installation_description2,
+        manufacturer_information,
+    };
+    Ok((i, configuration_information))
+}
+
+impl Message<ConfigurationInformation, &[u8]> for ConfigurationInformation {
+    fn get_data(data: &[u8]) -> Result<ConfigurationInformation, NmeaError> {
+        let parse_result: IResult<&[u8], ConfigurationInformation> =
+            nom::bits::bits(parse_configuration_information)(data);
+        match parse_result {
+            Ok((_, configuration_information)) => Ok(configuration_information),
+            Err(_e) => Err(NmeaError::ParseError),
+        }
+    }
+}
+
+impl Encode<Vec<u8>> for ConfigurationInformation {
+    fn encode(&self) -> Vec<u8> {
+        let mut writer = FieldWriter::new();
+        writer.put_lau_string(&self.installation_description1);
+        writer.put_lau_string(&self.installation_description2);
+        writer.put_lau_string(&self.manufacturer_information);
+        writer.into_payload()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_iso_request() {
+        let data = [0x14, 0xF0, 0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+        let request = ISORequest::get_data(ISORequestFrame { data }).unwrap();
+        assert_eq!(126996, request.pgn);
+        assert_eq!(Some(PGN::ProductInformation), request.get_pgn());
+        assert_eq!(data, request.encode().data);
+    }
+
+    #[test]
+    fn encodes_nak() {
+        let data = ISOAcknowledgement::nak(65280).encode().data;
+        assert_eq!([0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0xFF, 0x00], data);
+        let acknowledgement =
+            ISOAcknowledgement::get_data(ISOAcknowledgementFrame { data }).unwrap();
+        assert_eq!(Some(AcknowledgementControl::Nak), acknowledgement.control);
+        assert_eq!(65280, acknowledgement.pgn);
+    }
+
+    #[test]
+    fn parses_address_claim() {
+        let data = [0x39, 0x30, 0x60, 0x10, 0x00, 0x82, 0x78, 0xC0];
+        let claim = ISOAddressClaim::get_data(ISOAddressClaimFrame { data }).unwrap();
+        assert_eq!(12345, claim.name.unique_number());
+        assert_eq!(131, claim.name.manufacturer_code());
+        assert_eq!(130, claim.name.device_function());
+        assert_eq!(60, claim.name.device_class());
+        assert_eq!(4, claim.name.industry_group());
+        assert!(claim.name.arbitrary_address_capable());
+        assert_eq!(data, claim.encode().data);
+    }
+
+    #[test]
+    fn parses_heartbeat() {
+        // Every 60 s, both controllers error active, operational
+        let data = [0x70, 0x17, 0x2A, 0xC0, 0xFF, 0xFF, 0xFF, 0xFF];
+        let heartbeat = Heartbeat::get_data(HeartbeatFrame { data }).unwrap();
+        assert_eq!(
+            FieldValue::Value(Duration::from_secs(60)),
+            heartbeat.interval
+        );
+        assert_eq!(42, heartbeat.sequence_counter);
+        assert_eq!(
+            Some(ControllerState::ErrorActive),
+            heartbeat.controller2_state
+        );
+        assert_eq!(
+            Some(EquipmentStatus::Operational),
+            heartbeat.equipment_status
+        );
+        assert_eq!(data, heartbeat.encode().data);
+
+        let mut heartbeat = heartbeat;
+        heartbeat.interval = FieldValue::Value(Duration::from_secs(700));
+        assert_eq!([0xFE, 0xFF], heartbeat.encode().data[..2]);
+        heartbeat.interval = FieldValue::Value(Duration::from_millis(655_320));
+        assert_eq!([0xFC, 0xFF], heartbeat.encode().data[..2]);
+    }
+
+    #[test]
+    fn round_trips_product_information() {
+        let product_information = ProductInformation {
+            nmea_2000_version: FieldValue::Value(2100),
+            product_code: FieldValue::Value(1234),
+            model_id: "Tin Can Gateway".to_string(),
+            software_version: "0.1.0".to_string(),
+            model_version: "A".to_string(),
+            model_serial_code: "00042".to_string(),
+            certification_level: FieldValue::Value(1),
+            load_equivalency: FieldValue::Value(2),
+        };
+        let payload = product_information.encode();
+        assert_eq!(134, payload.len());
+        assert_eq!(0xFF, payload[4 + 15]);
+        let decoded = ProductInformation::get_data(&payload).unwrap();
+        assert_eq!(product_information, decoded);
+        assert_eq!(0.1, decoded.get_load_current().unwrap().get_amperes());
+    }
+
+    #[test]
+    fn round_trips_configuration_information() {
+        let configuration_information = ConfigurationInformation {
+            installation_description1: "Mast head".to_string(),
+            installation_description2: String::new(),
+            manufacturer_information: "tin_can".to_string(),
+        };
+        let payload = configuration_information.encode();
+        assert_eq!([11, 1, b'M'], payload[..3]);
+        assert_eq!([2, 1, 9, 1], payload[11..15]);
+        assert_eq!(
+            configuration_information,
+            ConfigurationInformation::get_data(&payload).unwrap()
+        );
+    }
+}
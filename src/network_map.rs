@@ -0,0 +1,228 @@
+//! An inventory of the devices on the bus, built from their network
+//! management traffic.
+//!
+//! Devices are identified by their NAME, so a device that has to move to
+//! another source address keeps its history. As with the AIS target table,
+//! callers pass in `now` as a monotonic timestamp.
+
+use core::time::Duration;
+use std::collections::{BTreeSet, HashMap};
+
+use crate::network_management::{Heartbeat, ISOAddressClaim, ISOName, ProductInformation};
+
+/// Address a device claims from when it could not get one of its own.
+const NULL_ADDRESS: u8 = 254;
+
+/// Certified devices send a heartbeat at least this often.
+const DEFAULT_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Device {
+    pub name: ISOName,
+    /// `None` once another device has taken the address, or the device
+    /// failed to claim one.
+    pub address: Option<u8>,
+    pub product_information: Option<ProductInformation>,
+    pub heartbeat_interval: Option<Duration>,
+    pub last_seen: Duration,
+    /// Every PGN seen from this device while it held its address.
+    pub transmitted_pgns: BTreeSet<u32>,
+}
+
+impl Device {
+    fn new(name: ISOName, now: Duration) -> Self {
+        Device {
+            name,
+            address: None,
+            product_information: None,
+            heartbeat_interval: None,
+            last_seen: now,
+            transmitted_pgns: BTreeSet::new(),
+        }
+    }
+
+    pub fn get_manufacturer_code(&self) -> u16 {
+        self.name.manufacturer_code()
+    }
+
+    pub fn get_model_id(&self) -> Option<&str> {
+        Some(&self.product_information.as_ref()?.model_id)
+    }
+
+    pub fn get_software_version(&self) -> Option<&str> {
+        Some(&self.product_information.as_ref()?.software_version)
+    }
+
+    /// A device is silent once it has missed two heartbeats.
+    pub fn is_silent(&self, now: Duration) -> bool {
+        let interval = self
+            .heartbeat_interval
+            .unwrap_or(DEFAULT_HEARTBEAT_INTERVAL);
+        now.saturating_sub(self.last_seen) > interval * 2
+    }
+}
+
+pub struct NetworkMap {
+    devices: HashMap<ISOName, Device>,
+    addresses: HashMap<u8, ISOName>,
+}
+
+impl NetworkMap {
+    pub fn new() -> Self {
+        NetworkMap {
+            devices: HashMap::new(),
+            addresses: HashMap::new(),
+        }
+    }
+
+    /// Records an address claim sent from `source`. A device claiming a new
+    /// address releases its old one, and takes this one over from whichever
+    /// device held it before.
+    pub fn update_address_claim(&mut self, source: u8, claim: &ISOAddressClaim, now: Duration) {
+        let name = claim.name;
+        let device = self
+            .devices
+            .entry(name)
+            .or_insert_with(|| Device::new(name, now));
+        device.last_seen = now;
+        if device.address == Some(source) {
+            return;
+        }
+        if let Some(old_address) = device.address.take() {
+            self.addresses.remove(&old_address);
+        }
+        if source == NULL_ADDRESS {
+            return;
+        }
+        device.address = Some(source);
+        if let Some(previous) = self.addresses.insert(source, name) {
+            if let Some(previous) = self.devices.get_mut(&previous) {
+                previous.address = None;
+            }
+        }
+    }
+
+    pub fn update_product_information(
+        &mut self,
+        source: u8,
+        product_information: &ProductInformation,
+        now: Duration,
+    ) {
+        if let Some(device) = self.device_at(source, now) {
+            device.product_information = Some(product_information.clone());
+        }
+    }
+
+    pub fn update_heartbeat(&mut self, source: u8, heartbeat: &Heartbeat, now: Duration) {
+        if let Some(device) = self.device_at(source, now) {
+            device.heartbeat_interval = heartbeat.interval.value();
+        }
+    }
+
+    /// Records any message seen from `source`. Messages from addresses that
+    /// have not been claimed yet are ignored.
+    pub fn record(&mut self, source: u8, pgn: u32, now: Duration) {
+        if let Some(device) = self.device_at(source, now) {
+            device.transmitted_pgns.insert(pgn);
+        }
+    }
+
+    fn device_at(&mut self, source: u8, now: Duration) -> Option<&mut Device> {
+        let name = self.addresses.get(&source)?;
+        let device = self.devices.get_mut(name)?;
+        device.last_seen = now;
+        Some(device)
+    }
+
+    pub fn get(&self, name: ISOName) -> Option<&Device> {
+        self.devices.get(&name)
+    }
+
+    pub fn get_by_address(&self, address: u8) -> Option<&Device> {
+        self.devices.get(self.addresses.get(&address)?)
+    }
+
+    pub fn devices(&self) -> impl Iterator<Item = &Device> {
+        self.devices.values()
+    }
+
+    pub fn silent_devices(&self, now: Duration) -> impl Iterator<Item = &Device> {
+        self.devices
+            .values()
+            .filter(move |device| device.is_silent(now))
+    }
+}
+
+impl Default for NetworkMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_value::FieldValue;
+
+    fn claim(unique_number: u32) -> ISOAddressClaim {
+        let mut name = ISOName(0);
+        name.set_unique_number(unique_number);
+        name.set_manufacturer_code(137);
+        ISOAddressClaim { name }
+    }
+
+    #[test]
+    fn follows_devices_across_address_changes() {
+        let mut map = NetworkMap::new();
+        let gps = claim(1);
+        let sounder = claim(2);
+        map.update_address_claim(10, &gps, Duration::from_secs(0));
+        map.record(10, 129025, Duration::from_secs(1));
+        // The sounder wins address 10, the GPS moves to 11
+        map.update_address_claim(10, &sounder, Duration::from_secs(2));
+        assert_eq!(None, map.get(gps.name).unwrap().address);
+        map.update_address_claim(11, &gps, Duration::from_secs(3));
+        map.record(11, 129026, Duration::from_secs(4));
+        map.record(10, 128267, Duration::from_secs(4));
+
+        let device = map.get_by_address(11).unwrap();
+        assert_eq!(gps.name, device.name);
+        assert_eq!(137, device.get_manufacturer_code());
+        assert_eq!(
+            vec![129025, 129026],
+            device
+                .transmitted_pgns
+                .iter()
+                .copied()
+                .collect::<Vec<u32>>()
+        );
+        assert_eq!(Duration::from_secs(4), device.last_seen);
+        assert_eq!(sounder.name, map.get_by_address(10).unwrap().name);
+        assert_eq!(2, map.devices().count());
+    }
+
+    #[test]
+    fn flags_silent_devices() {
+        let mut map = NetworkMap::new();
+        map.update_address_claim(10, &claim(1), Duration::from_secs(0));
+        map.update_address_claim(11, &claim(2), Duration::from_secs(0));
+        map.update_heartbeat(
+            11,
+            &Heartbeat {
+                interval: FieldValue::Value(Duration::from_secs(10)),
+                sequence_counter: 0,
+                controller1_state: None,
+                controller2_state: None,
+                equipment_status: None,
+            },
+            Duration::from_secs(0),
+        );
+
+        let silent: Vec<Option<u8>> = map
+            .silent_devices(Duration::from_secs(21))
+            .map(|device| device.address)
+            .collect();
+        assert_eq!(vec![Some(11)], silent);
+        assert_eq!(2, map.silent_devices(Duration::from_secs(121)).count());
+    }
+}
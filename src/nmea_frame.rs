@@ -1,19 +1,20 @@
+use crate::pgn::PGN;
+
+include!(concat!(env!("OUT_DIR"), "/nmea_frame.rs"));
+
+/// One raw frame of a Navigation Data fast packet, as taken by
+/// `NavigationData::parse_frame`.
 pub struct NavigationDataFrame {
     pub data: [u8; 8],
 }
-pub struct COGSOGRapidUpdateFrame {
-    pub data: [u8; 8],
-}
-pub struct SystemTimeFrame {
-    pub data: [u8; 8],
-}
-pub struct VesselHeadingFrame {
-    pub data: [u8; 8],
-}
 
-pub enum NmeaFrame {
-    SystemTime(SystemTimeFrame),
-    NavigationData(NavigationDataFrame),
-    COGSOGRapidUpdate(COGSOGRapidUpdateFrame),
-    VesselHeading(VesselHeadingFrame),
+/// The data of one PGN, complete and ready to be decoded.
+pub enum NmeaData {
+    /// A single frame PGN.
+    Frame(NmeaFrame),
+    /// The payload of a fast packet PGN, put back together from its frames.
+    FastPacket(PGN, Vec<u8>),
+    /// A PGN missing from `pgns.json`, such as a proprietary one, with its
+    /// frame or reassembled fast packet payload.
+    Unknown(u32, Vec<u8>),
 }
@@ -1,6 +1,8 @@
 use nom::bits::streaming::take;
 use nom::IResult;
 
+use crate::NmeaError;
+
 pub type BitInput<'a> = (&'a [u8], usize);
 
 pub fn take_i32(i: BitInput) -> IResult<BitInput, i32> {
@@ -8,16 +10,16 @@ pub fn take_i32(i: BitInput) -> IResult<BitInput, i32> {
     Ok((i, data.to_be()))
 }
 
-pub fn take_i16(i: BitInput) -> IResult<BitInput, i16> {
-    let (i, data): (BitInput, i16) = take(16usize)(i)?;
-    Ok((i, data.to_be()))
-}
-
 pub fn take_u32(i: BitInput) -> IResult<BitInput, u32> {
     let (i, data): (BitInput, u32) = take(32usize)(i)?;
     Ok((i, data.to_be()))
 }
 
+pub fn take_u24(i: BitInput) -> IResult<BitInput, u32> {
+    let (i, data): (BitInput, u32) = take(24usize)(i)?;
+    Ok((i, data.to_be() >> 8))
+}
+
 pub fn take_u16(i: BitInput) -> IResult<BitInput, u16> {
     let (i, data): (BitInput, u16) = take(16usize)(i)?;
     Ok((i, data.to_be()))
@@ -28,28 +30,68 @@ pub fn take_byte(i: BitInput) -> IResult<BitInput, u8> {
     Ok((i, data.to_be()))
 }
 
-pub fn take_nibble(i: BitInput) -> IResult<BitInput, u8> {
-    let (i, data): (BitInput, u8) = take(4usize)(i)?;
-    Ok((i, data.to_be()))
-}
-
-pub fn take_two_bits(i: BitInput) -> IResult<BitInput, u8> {
-    let (i, data): (BitInput, u8) = take(2usize)(i)?;
-    Ok((i, data.to_be()))
-}
-
 pub fn take_five_bits(i: BitInput) -> IResult<BitInput, u8> {
     let (i, data): (BitInput, u8) = take(5usize)(i)?;
     Ok((i, data.to_be()))
 }
 
 pub fn take_three_bits(i: BitInput) -> IResult<BitInput, u8> {
-    println!("{:?}", i);
     let (i, data): (BitInput, u8) = take(3usize)(i)?;
     Ok((i, data.to_be()))
 }
 
+#[allow(dead_code)]
 pub fn take_bit(i: BitInput) -> IResult<BitInput, bool> {
     let (i, data): (BitInput, u8) = take(1usize)(i)?;
     Ok((i, data.to_be() == 1))
 }
+
+pub fn take_bytes(count: usize) -> impl Fn(BitInput) -> IResult<BitInput, Vec<u8>> {
+    move |i: BitInput| nom::multi::count(take_byte, count)(i)
+}
+
+/// A fixed length ASCII field. Senders pad these with `@`, spaces, NULs or
+/// 0xFF, all of which are trimmed from the end.
+pub fn take_fixed_string(count: usize) -> impl Fn(BitInput) -> IResult<BitInput, String> {
+    move |i: BitInput| {
+        let (i, raw) = take_bytes(count)(i)?;
+        let text: String = raw.iter().map(|&byte| byte as char).collect();
+        let trimmed = text.trim_end_matches(['@', ' ', '\u{0}', '\u{ff}']);
+        Ok((i, trimmed.to_string()))
+    }
+}
+
+/// A variable length string: a length byte that counts itself and the
+/// encoding byte, an encoding byte (0 is UTF-16LE, 1 is ASCII) and the text.
+pub fn take_lau_string(i: BitInput) -> IResult<BitInput, String> {
+    let (i, length) = take_byte(i)?;
+    let (i, encoding) = take_byte(i)?;
+    let (i, raw) = take_bytes(usize::from(length.saturating_sub(2)))(i)?;
+    let text = if encoding == 0 {
+        let units = raw
+            .chunks_exact(2)
+            .map(|unit| u16::from_le_bytes([unit[0], unit[1]]));
+        char::decode_utf16(units)
+            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect()
+    } else {
+        raw.iter().map(|&byte| byte as char).collect::<String>()
+    };
+    let trimmed = text.trim_end_matches(['@', ' ', '\u{0}', '\u{ff}']);
+    Ok((i, trimmed.to_string()))
+}
+
+/// Runs `parser` over `data` from byte `offset`, for the strings around the
+/// fields a message's layout decodes.
+pub fn parse_at<'a, T>(
+    data: &'a [u8],
+    offset: usize,
+    parser: impl FnMut(BitInput<'a>) -> IResult<BitInput<'a>, T>,
+) -> Result<T, NmeaError> {
+    let input = data.get(offset..).ok_or(NmeaError::ParseError)?;
+    let parse_result: IResult<&'a [u8], T> = nom::bits::bits(parser)(input);
+    match parse_result {
+        Ok((_, value)) => Ok(value),
+        Err(_e) => Err(NmeaError::ParseError),
+    }
+}
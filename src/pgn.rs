@@ -1,14 +1,340 @@
 use num_derive::FromPrimitive;
 
-#[derive(FromPrimitive, Debug, PartialEq, Eq)]
-pub enum PGN {
-    SystemTime = 126992,
-    VesselHeading = 127250,
-    NavigationData = 129284,
-    GNSSPositionData = 129029,
-    COGSOGRapidUpdate = 129026,
-    PositionRapidUpdate = 129025,
-    GNSSSatsInView = 129540,
-    WindData = 130306,
-    ISOAddressClaim = 60928,
+use crate::field_value::FieldValue;
+
+include!(concat!(env!("OUT_DIR"), "/pgn.rs"));
+
+/// How the payload of a PGN is carried on the bus.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PgnKind {
+    /// Fits in a single 8 byte CAN frame.
+    Single,
+    /// Split over up to 32 frames using the NMEA 2000 fast packet protocol.
+    FastPacket,
+    /// Sent with the ISO transport protocol, in TP.CM and TP.DT frames of
+    /// their own PGNs.
+    TransportProtocol,
+}
+
+/// Position and scaling of one field within a PGN's payload.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct FieldInfo {
+    /// Field number, starting at 1, as used by group functions.
+    pub order: u8,
+    pub id: &'static str,
+    pub bit_length: u16,
+    pub bit_offset: u16,
+    pub signed: bool,
+    /// The value is `(raw + offset) * resolution`.
+    pub resolution: f64,
+    pub offset: i64,
+    /// An enumerated value or raw bits, where only all ones is reserved,
+    /// as not available.
+    pub lookup: bool,
+}
+
+impl FieldInfo {
+    fn max_raw(&self) -> u64 {
+        u64::MAX >> (64 - self.bit_length.clamp(1, 64))
+    }
+
+    /// The raw bits of this field, `None` when `data` is too short.
+    pub fn read_raw(&self, data: &[u8]) -> Option<u64> {
+        let offset = usize::from(self.bit_offset);
+        let length = usize::from(self.bit_length);
+        if length == 0 || length > 64 || offset + length > data.len() * 8 {
+            return None;
+        }
+        Some((0..length).fold(0, |raw, bit| {
+            let position = offset + bit;
+            let value = (data[position / 8] >> (position % 8)) & 1;
+            raw | u64::from(value) << bit
+        }))
+    }
+
+    pub fn write_raw(&self, data: &mut [u8], raw: u64) {
+        let offset = usize::from(self.bit_offset);
+        for bit in 0..usize::from(self.bit_length).min(64) {
+            let position = offset + bit;
+            let Some(byte) = data.get_mut(position / 8) else {
+                return;
+            };
+            let mask = 1 << (position % 8);
+            if raw >> bit & 1 == 1 {
+                *byte |= mask;
+            } else {
+                *byte &= !mask;
+            }
+        }
+    }
+
+    /// The raw value as a number, sign extended for signed fields, and
+    /// classified against the values reserved at the top of its range.
+    fn classify(&self, raw: u64) -> FieldValue<i64> {
+        // Single bit lookups are plain flags with no value to spare
+        if self.lookup && self.bit_length > 1 {
+            return if raw == self.max_raw() {
+                FieldValue::NotAvailable
+            } else {
+                FieldValue::Value(raw as i64)
+            };
+        }
+        let (value, max) = if self.signed {
+            let sign = 1u64 << (self.bit_length - 1);
+            let value = if raw & sign != 0 {
+                (raw | !self.max_raw()) as i64
+            } else {
+                raw as i64
+            };
+            (value, (self.max_raw() >> 1) as i64)
+        } else {
+            (raw as i64, self.max_raw() as i64)
+        };
+        match self.bit_length {
+            1 => FieldValue::Value(value),
+            2 => FieldValue::from_two_bits(raw as u8).map(|_| value),
+            _ if value == max => FieldValue::NotAvailable,
+            _ if value == max - 1 => FieldValue::OutOfRange,
+            _ if value == max - 2 => FieldValue::Reserved,
+            _ => FieldValue::Value(value),
+        }
+    }
+
+    /// Reads the field and applies its offset and resolution.
+    pub fn read(&self, data: &[u8]) -> Option<FieldValue<f64>> {
+        let raw = self.read_raw(data)?;
+        Some(
+            self.classify(raw)
+                .map(|value| self.scale(value + self.offset)),
+        )
+    }
+
+    /// Decimal resolutions such as 0.01 have no exact binary value, so they
+    /// are applied by dividing by their inverse: 1234 reads as 12.34 rather
+    /// than 12.340000000000002.
+    fn scale(&self, value: i64) -> f64 {
+        match self.decimal_inverse() {
+            Some(inverse) => value as f64 / inverse,
+            None => value as f64 * self.resolution,
+        }
+    }
+
+    fn decimal_inverse(&self) -> Option<f64> {
+        let inverse = (1.0 / self.resolution).round();
+        (self.resolution < 1.0 && (inverse * self.resolution - 1.0).abs() < 1e-9).then_some(inverse)
+    }
+
+    /// The raw number for a scaled value, undoing the offset and resolution.
+    pub fn to_raw(&self, value: f64) -> i64 {
+        let raw = match self.decimal_inverse() {
+            Some(inverse) => value * inverse,
+            None => value / self.resolution,
+        };
+        raw.round() as i64 - self.offset
+    }
+
+    /// Writes a scaled value, sending values that do not fit as out of
+    /// range, or as not available for lookups.
+    pub fn write(&self, data: &mut [u8], value: FieldValue<f64>) {
+        let max = self.max_raw();
+        let top = if self.signed { max >> 1 } else { max };
+        let raw = match value {
+            FieldValue::Value(value) if self.lookup => {
+                let raw = self.to_raw(value);
+                if raw < 0 || raw >= max as i64 {
+                    max
+                } else {
+                    raw as u64
+                }
+            }
+            _ if self.lookup => max,
+            FieldValue::Value(value) => {
+                let raw = self.to_raw(value);
+                let min = if self.signed { -(top as i64) - 1 } else { 0 };
+                let reserved = if self.bit_length > 2 { 3 } else { 0 };
+                if raw < min || raw > top as i64 - reserved {
+                    top.saturating_sub(1)
+                } else {
+                    raw as u64 & max
+                }
+            }
+            FieldValue::NotAvailable => top,
+            FieldValue::Reserved if self.bit_length > 2 => top - 2,
+            _ => top.saturating_sub(1),
+        };
+        self.write_raw(data, raw);
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PgnInfo {
+    pub pgn: u32,
+    pub name: &'static str,
+    /// Default priority, 0 being the highest.
+    pub priority: u8,
+    /// Nominal payload length in bytes.
+    pub length: u16,
+    pub kind: PgnKind,
+    /// Default transmit interval in milliseconds, `None` for messages that
+    /// are only sent on request or on change.
+    pub transmit_interval: Option<u16>,
+    /// Field layout, empty when `pgns.json` does not describe it.
+    pub fields: &'static [FieldInfo],
+}
+
+include!(concat!(env!("OUT_DIR"), "/pgn_info.rs"));
+
+/// Looks up the metadata of a PGN listed in `pgns.json`.
+pub fn pgn_info(pgn: u32) -> Option<&'static PgnInfo> {
+    PGN_INFO
+        .binary_search_by_key(&pgn, |info| info.pgn)
+        .ok()
+        .map(|index| &PGN_INFO[index])
+}
+
+impl PgnInfo {
+    /// Returns the metadata for any raw PGN. PGNs that are not in the table
+    /// get defaults derived from the range they fall in.
+    pub fn from_raw(pgn: u32) -> PgnInfo {
+        if let Some(info) = pgn_info(pgn) {
+            return *info;
+        }
+        let kind = match pgn {
+            // Addressed and proprietary fast packet ranges
+            126208..=126720 | 130816..=131071 => PgnKind::FastPacket,
+            _ => PgnKind::Single,
+        };
+        PgnInfo {
+            pgn,
+            name: "Unknown",
+            priority: 6,
+            length: match kind {
+                PgnKind::Single => 8,
+                _ => 223,
+            },
+            kind,
+            transmit_interval: None,
+            fields: &[],
+        }
+    }
+
+    pub fn field(&self, order: u8) -> Option<&'static FieldInfo> {
+        self.fields.iter().find(|field| field.order == order)
+    }
+
+    /// A payload with every field not available, long enough to hold all
+    /// fields and at least a full frame for single frame PGNs.
+    pub fn empty_payload(&self) -> Vec<u8> {
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| {
+                (usize::from(field.bit_offset) + usize::from(field.bit_length)).div_ceil(8)
+            })
+            .max()
+            .unwrap_or(0);
+        let length = match self.kind {
+            PgnKind::Single => fields.max(8),
+            _ => fields,
+        };
+        vec![0xFF; length]
+    }
+}
+
+/// Whether `pgn` is reserved for manufacturer specific messages.
+pub fn is_proprietary(pgn: u32) -> bool {
+    matches!(pgn, 61184 | 65280..=65535 | 126720 | 130816..=131071)
+}
+
+impl PGN {
+    pub fn info(self) -> &'static PgnInfo {
+        pgn_info(self as u32).expect("every PGN variant is generated from the metadata table")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn looks_up_known_pgn() {
+        let info = PGN::NavigationData.info();
+        assert_eq!(129284, info.pgn);
+        assert_eq!("Navigation Data", info.name);
+        assert_eq!(PgnKind::FastPacket, info.kind);
+        assert_eq!(3, info.priority);
+        assert_eq!(Some(1000), info.transmit_interval);
+    }
+
+    #[test]
+    fn derives_info_for_unknown_pgn_from_range() {
+        assert_eq!(PgnKind::FastPacket, PgnInfo::from_raw(130900).kind);
+        assert_eq!(PgnKind::Single, PgnInfo::from_raw(65300).kind);
+        assert_eq!(None, pgn_info(65300));
+    }
+
+    #[test]
+    fn looks_up_field_layout() {
+        let field = PGN::BatteryStatus.info().field(3).unwrap();
+        assert_eq!("current", field.id);
+        assert_eq!(24, field.bit_offset);
+        assert!(field.signed);
+        assert!(PgnInfo::from_raw(65300).fields.is_empty());
+    }
+
+    #[test]
+    fn reads_and_writes_scaled_fields() {
+        let info = PGN::BatteryStatus.info();
+        let mut data = info.empty_payload();
+        let current = info.field(3).unwrap();
+        current.write(&mut data, FieldValue::Value(-12.5));
+        assert_eq!([0x83, 0xFF], data[3..5]);
+        assert_eq!(Some(FieldValue::Value(-12.5)), current.read(&data));
+        current.write(&mut data, FieldValue::Value(5000.0));
+        assert_eq!(Some(FieldValue::OutOfRange), current.read(&data));
+        let voltage = info.field(2).unwrap();
+        voltage.write(&mut data, FieldValue::NotAvailable);
+        assert_eq!([0xFF, 0x7F], data[1..3]);
+        assert_eq!(Some(FieldValue::NotAvailable), voltage.read(&data));
+        assert_eq!(None, current.read(&data[..4]));
+    }
+
+    #[test]
+    fn passes_lookup_values_through() {
+        let fluid = PGN::FluidLevel.info();
+        let fluid_type = fluid.field(2).unwrap();
+        assert_eq!("type", fluid_type.id);
+        assert_eq!(
+            Some(FieldValue::Value(14.0)),
+            fluid_type.read(&[0xE0, 0, 0, 0, 0, 0, 0, 0])
+        );
+        assert_eq!(
+            Some(FieldValue::NotAvailable),
+            fluid_type.read(&[0xF0, 0, 0, 0, 0, 0, 0, 0])
+        );
+
+        let heading = PGN::SeatalkPilotLockedHeading.info();
+        let industry = heading.field(3).unwrap();
+        assert_eq!("industryCode", industry.id);
+        let mut data = heading.empty_payload();
+        industry.write(&mut data, FieldValue::Value(5.0));
+        assert_eq!(0xBF, data[1]);
+        assert_eq!(Some(FieldValue::Value(5.0)), industry.read(&data));
+
+        let switches = PGN::BinarySwitchBankStatus.info();
+        let indicator = switches.field(2).unwrap();
+        assert_eq!(
+            Some(FieldValue::Value(2.0)),
+            indicator.read(&[0, 0x02, 0, 0, 0, 0, 0, 0])
+        );
+        indicator.write(&mut data, FieldValue::Error);
+        assert_eq!(Some(FieldValue::NotAvailable), indicator.read(&data));
+
+        let report = PGN::AISClassAPositionReport.info();
+        let accuracy = report.field(6).unwrap();
+        assert_eq!("positionAccuracy", accuracy.id);
+        let mut data = report.empty_payload();
+        data[13] = 0x01;
+        assert_eq!(Some(FieldValue::Value(1.0)), accuracy.read(&data));
+    }
 }
@@ -0,0 +1,77 @@
+//! Messages decoded straight from the field layouts in `pgns.json`, one
+//! `<Id>Fields` struct per PGN that lists its fields, with a `to_frame` for
+//! single frame PGNs. Every value is scaled by its field's resolution and
+//! offset, so adding a PGN with a layout is only a change to `pgns.json`.
+//! The hand written messages decode and encode through these structs and
+//! only add units and enums.
+
+use crate::{field_value::FieldValue, pgn::PGN, Encode, Message, NmeaError};
+
+include!(concat!(env!("OUT_DIR"), "/pgn_fields.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{electrical::BatteryStatus, nmea_frame::BatteryStatusFrame};
+
+    #[test]
+    fn matches_hand_written_decoder() {
+        let data = [0x01, 0xB0, 0x04, 0x83, 0xFF, 0x51, 0x72, 0xFF];
+        let fields = BatteryStatusFields::get_data(&data).unwrap();
+        let battery = BatteryStatus::get_data(BatteryStatusFrame { data }).unwrap();
+
+        assert_eq!(FieldValue::Value(1.0), fields.instance);
+        assert_eq!(
+            battery.voltage.value().unwrap().get_volts(),
+            fields.voltage.value().unwrap() as f32
+        );
+        assert_eq!(FieldValue::Value(-12.5), fields.current);
+        assert_eq!(
+            battery.temperature.value().unwrap().get_kelvin(),
+            fields.temperature.value().unwrap() as f32
+        );
+        assert_eq!(FieldValue::NotAvailable, fields.sid);
+        assert_eq!(data.to_vec(), fields.encode());
+    }
+
+    #[test]
+    fn applies_signed_fields_and_offsets() {
+        let data = [12, 0xE2, 0x04, 0, 0, 0x0C, 0xFE, 10];
+        let depth = WaterDepthFields::get_data(&data).unwrap();
+        assert_eq!(FieldValue::Value(12.5), depth.depth);
+        assert_eq!(FieldValue::Value(-0.5), depth.offset);
+        assert_eq!(FieldValue::Value(100.0), depth.range);
+        assert_eq!(data.to_vec(), depth.encode());
+
+        let data = [0x0C, 0x92, 0x35, 0x77, 0x00, 0x60, 0xFD, 0xFF];
+        let reactive = UtilityTotalACReactivePowerFields::get_data(&data).unwrap();
+        assert_eq!(FieldValue::Value(-500.0), reactive.reactive_power);
+        assert_eq!(FieldValue::Value(0.5), reactive.power_factor);
+        assert_eq!(FieldValue::Value(1.0), reactive.power_factor_lagging);
+        assert_eq!(data.to_vec(), reactive.encode());
+    }
+
+    #[test]
+    fn decodes_gnss_position_without_a_hand_written_decoder() {
+        // 52.3702157 N 4.8952 E, 1.5 m below the ellipsoid, GPS+GLONASS
+        // with differential corrections from 9 satellites
+        let data = [
+            0x01, 0xA9, 0x4C, 0x00, 0x51, 0x25, 0x02, 0x00, 0x42, 0x17, 0x4C, 0x56, 0x90, 0x44,
+            0x07, 0x00, 0x80, 0x0B, 0xAB, 0x95, 0xE9, 0xAD, 0x00, 0xA0, 0x1C, 0xE9, 0xFF, 0xFF,
+            0xFF, 0xFF, 0xFF, 0x23, 0xFC, 0x09, 0x50, 0x00, 0x96, 0x00, 0x5C, 0x12, 0x00, 0x00,
+            0x00,
+        ];
+        let position = GNSSPositionDataFields::get_data(&data).unwrap();
+        assert_eq!(FieldValue::Value(19625.0), position.date);
+        assert_eq!(FieldValue::Value(3600.0), position.time);
+        assert_eq!(FieldValue::Value(52.3702157), position.latitude);
+        assert_eq!(FieldValue::Value(4.8952), position.longitude);
+        assert_eq!(FieldValue::Value(-1.5), position.altitude);
+        assert_eq!(FieldValue::Value(3.0), position.gnss_type);
+        assert_eq!(FieldValue::Value(2.0), position.method);
+        assert_eq!(FieldValue::Value(9.0), position.number_of_svs);
+        assert_eq!(FieldValue::Value(0.8), position.hdop);
+        assert_eq!(FieldValue::Value(47.0), position.geoidal_separation);
+        assert_eq!(data.to_vec(), position.encode());
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::BTreeSet;
+
+use nom::{multi::count, IResult};
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use crate::{
+    network_management::ISORequest,
+    parse::{take_byte, take_u24, BitInput},
+    pgn::PGN,
+    write::FieldWriter,
+    Encode, Message, NmeaError,
+};
+
+#[derive(FromPrimitive, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PgnListFunction {
+    Transmit = 0,
+    Receive = 1,
+}
+
+/// PGN 126464, a fast packet message. Devices answer an ISO Request for it
+/// with one list of the PGNs they transmit and one of those they receive.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PgnList {
+    pub function: Option<PgnListFunction>,
+    pub pgns: Vec<u32>,
+}
+
+impl PgnList {
+    /// The listed PGNs this crate knows.
+    pub fn known_pgns(&self) -> impl Iterator<Item = PGN> + '_ {
+        self.pgns
+            .iter()
+            .filter_map(|&pgn| FromPrimitive::from_u32(pgn))
+    }
+}
+
+fn parse_pgn_list(i: BitInput) -> IResult<BitInput, PgnList> {
+    let (i, function) = take_byte(i)?;
+    let entries = i.0.len() / 3;
+    let (i, pgns) = count(take_u24, entries)(i)?;
+
+    let pgn_list = PgnList {
+        function: FromPrimitive::from_u8(function),
+        // Fast packet padding reads as 0xFFFFFF
+        pgns: pgns.into_iter().filter(|&pgn| pgn != 0xFFFFFF).collect(),
+    };
+    Ok((i, pgn_list))
+}
+
+impl Message<PgnList, &[u8]> for PgnList {
+    fn get_data(data: &[u8]) -> Result<PgnList, NmeaError> {
+        let parse_result: IResult<&[u8], PgnList> = nom::bits::bits(parse_pgn_list)(data);
+        match parse_result {
+            Ok((_, pgn_list)) => Ok(pgn_list),
+            Err(_e) => Err(NmeaError::ParseError),
+        }
+    }
+}
+
+impl Encode<Vec<u8>> for PgnList {
+    fn encode(&self) -> Vec<u8> {
+        let mut writer = FieldWriter::new();
+        writer.put_u8(self.function.map_or(0xFF, |function| function as u8));
+        for pgn in &self.pgns {
+            writer.put_u24(*pgn);
+        }
+        writer.into_payload()
+    }
+}
+
+/// Asks one device for its PGN lists and collects the answers.
+///
+/// Send [`PgnListQuery::request`] to `destination`, then pass every PGN List
+/// received to [`PgnListQuery::handle`] until it reports completion.
+pub struct PgnListQuery {
+    destination: u8,
+    transmitted: Option<BTreeSet<u32>>,
+    received: Option<BTreeSet<u32>>,
+}
+
+impl PgnListQuery {
+    pub fn new(destination: u8) -> Self {
+        PgnListQuery {
+            destination,
+            transmitted: None,
+            received: None,
+        }
+    }
+
+    pub fn get_destination(&self) -> u8 {
+        self.destination
+    }
+
+    pub fn request(&self) -> ISORequest {
+        ISORequest {
+            pgn: PGN::PGNListTransmitAndReceive as u32,
+        }
+    }
+
+    /// Records a PGN List sent by `source`, returning true once both lists
+    /// from the queried device have arrived.
+    pub fn handle(&mut self, source: u8, pgn_list: &PgnList) -> bool {
+        if source == self.destination {
+            let pgns = pgn_list.pgns.iter().copied().collect();
+            match pgn_list.function {
+                Some(PgnListFunction::Transmit) => self.transmitted = Some(pgns),
+                Some(PgnListFunction::Receive) => self.received = Some(pgns),
+                None => {}
+            }
+        }
+        self.is_complete()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.transmitted.is_some() && self.received.is_some()
+    }
+
+    pub fn transmitted_pgns(&self) -> Option<&BTreeSet<u32>> {
+        self.transmitted.as_ref()
+    }
+
+    pub fn received_pgns(&self) -> Option<&BTreeSet<u32>> {
+        self.received.as_ref()
+    }
+
+    /// The PGNs in `expected` the device does not list as transmitted, or
+    /// `None` before its transmit list has arrived.
+    pub fn missing_transmitted(&self, expected: &[PGN]) -> Option<Vec<PGN>> {
+        let transmitted = self.transmitted.as_ref()?;
+        Some(
+            expected
+                .iter()
+                .copied()
+                .filter(|pgn| !transmitted.contains(&(*pgn as u32)))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pgn_list() {
+        let data = [
+            0x00, 0x00, 0xEE, 0x00, 0x14, 0xF2, 0x01, 0x00, 0xFF, 0x00, 0xFF, 0xFF, 0xFF,
+        ];
+        let pgn_list = PgnList::get_data(&data).unwrap();
+        assert_eq!(Some(PgnListFunction::Transmit), pgn_list.function);
+        assert_eq!(vec![60928, 127508, 65280], pgn_list.pgns);
+        assert_eq!(
+            vec![PGN::ISOAddressClaim, PGN::BatteryStatus],
+            pgn_list.known_pgns().collect::<Vec<PGN>>()
+        );
+        assert_eq!(data[..10].to_vec(), pgn_list.encode());
+    }
+
+    #[test]
+    fn collects_both_lists_from_queried_device() {
+        let mut query = PgnListQuery::new(35);
+        assert_eq!(126464, query.request().pgn);
+        let transmit = PgnList {
+            function: Some(PgnListFunction::Transmit),
+            pgns: vec![60928, 128267],
+        };
+        let receive = PgnList {
+            function: Some(PgnListFunction::Receive),
+            pgns: vec![59904],
+        };
+
+        assert!(!query.handle(36, &transmit));
+        assert_eq!(None, query.missing_transmitted(&[PGN::WaterDepth]));
+        assert!(!query.handle(35, &transmit));
+        assert!(query.handle(35, &receive));
+        assert_eq!(
+            Some(vec![PGN::Temperature]),
+            query.missing_transmitted(&[PGN::WaterDepth, PGN::Temperature])
+        );
+        assert!(query.received_pgns().unwrap().contains(&59904));
+    }
+}
@@ -0,0 +1,37 @@
+use crate::{
+    field_value::FieldValue,
+    nmea_frame::PositionRapidUpdateFrame,
+    pgn_fields::PositionRapidUpdateFields,
+    units::{Latitude, Longitude},
+    Message, NmeaError,
+};
+
+/// PGN 129025
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PositionRapidUpdate {
+    pub latitude: FieldValue<Latitude>,
+    pub longitude: FieldValue<Longitude>,
+}
+
+impl Message<PositionRapidUpdate, PositionRapidUpdateFrame> for PositionRapidUpdate {
+    fn get_data(frame: PositionRapidUpdateFrame) -> Result<PositionRapidUpdate, NmeaError> {
+        let fields = PositionRapidUpdateFields::get_data(&frame.data)?;
+        Ok(PositionRapidUpdate {
+            latitude: fields.latitude.map(Latitude::from_degrees),
+            longitude: fields.longitude.map(Longitude::from_degrees),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_position_rapid_update() {
+        let data = [0x8D, 0x0F, 0x37, 0x1F, 0xC0, 0xF2, 0xEA, 0x02];
+        let position = PositionRapidUpdate::get_data(PositionRapidUpdateFrame { data }).unwrap();
+        assert_eq!(52.3702157, position.latitude.value().unwrap().get_degrees());
+        assert_eq!(4.8952, position.longitude.value().unwrap().get_degrees());
+    }
+}
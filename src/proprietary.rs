@@ -0,0 +1,233 @@
+//! Manufacturer specific PGNs.
+//!
+//! Every proprietary payload starts with the sender's manufacturer and
+//! industry codes. [`ProprietaryDecoders`] reads those and hands the payload
+//! to the decoder registered for that manufacturer and PGN, or returns it
+//! raw when there is none.
+
+use std::collections::HashMap;
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use crate::{pgn::is_proprietary, NmeaError, NmeaId};
+
+/// Manufacturer codes assigned by the NMEA.
+#[derive(FromPrimitive, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Manufacturer {
+    Airmar = 135,
+    Maretron = 137,
+    Lowrance = 140,
+    MercuryMarine = 144,
+    VolvoPenta = 174,
+    Garmin = 229,
+    Actisense = 273,
+    Navico = 275,
+    VictronEnergy = 358,
+    BAndG = 381,
+    FusionElectronics = 419,
+    YachtDevices = 717,
+    Raymarine = 1851,
+    Furuno = 1855,
+    Simrad = 1857,
+    YamahaMarine = 1862,
+}
+
+#[derive(FromPrimitive, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum IndustryCode {
+    Global = 0,
+    Highway = 1,
+    Agriculture = 2,
+    Construction = 3,
+    Marine = 4,
+    Industrial = 5,
+}
+
+/// The first two bytes of a proprietary payload.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ProprietaryId {
+    pub manufacturer_code: u16,
+    pub industry_code: u8,
+}
+
+impl ProprietaryId {
+    /// 11 bits of manufacturer code, two reserved bits and three bits of
+    /// industry code.
+    pub fn from_raw(raw: u16) -> Self {
+        ProprietaryId {
+            manufacturer_code: raw & 0x07FF,
+            industry_code: (raw >> 13) as u8,
+        }
+    }
+
+    pub fn to_raw(self) -> u16 {
+        (self.manufacturer_code & 0x07FF) | 0x1800 | u16::from(self.industry_code & 0x07) << 13
+    }
+
+    /// A marine industry id for `manufacturer`.
+    pub fn marine(manufacturer: Manufacturer) -> Self {
+        ProprietaryId {
+            manufacturer_code: manufacturer as u16,
+            industry_code: IndustryCode::Marine as u8,
+        }
+    }
+
+    pub fn get_manufacturer(&self) -> Option<Manufacturer> {
+        FromPrimitive::from_u16(self.manufacturer_code)
+    }
+
+    pub fn get_industry(&self) -> Option<IndustryCode> {
+        FromPrimitive::from_u8(self.industry_code)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ProprietaryPayload<T> {
+    Decoded(T),
+    /// No decoder is registered for the message. Holds the whole payload,
+    /// including the two id bytes.
+    Raw(Vec<u8>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ProprietaryMessage<T> {
+    pub pgn: u32,
+    pub id: ProprietaryId,
+    pub payload: ProprietaryPayload<T>,
+}
+
+/// Decodes a whole proprietary payload, id bytes included. Decoders return
+/// `NmeaError::NotImplemented` for messages they do not handle, which are
+/// then passed on raw.
+pub type ProprietaryDecoder<T> = Box<dyn Fn(&[u8]) -> Result<T, NmeaError>>;
+
+/// Proprietary decoders keyed by manufacturer code and PGN. `T` is the
+/// caller's type for the messages it decodes.
+pub struct ProprietaryDecoders<T> {
+    decoders: HashMap<(u16, u32), ProprietaryDecoder<T>>,
+}
+
+impl<T> ProprietaryDecoders<T> {
+    pub fn new() -> Self {
+        ProprietaryDecoders {
+            decoders: HashMap::new(),
+        }
+    }
+
+    /// Registers `decoder` for `pgn` sent by `manufacturer_code`, replacing
+    /// any decoder registered before.
+    pub fn register<F>(&mut self, manufacturer_code: u16, pgn: u32, decoder: F)
+    where
+        F: Fn(&[u8]) -> Result<T, NmeaError> + 'static,
+    {
+        self.decoders
+            .insert((manufacturer_code, pgn), Box::new(decoder));
+    }
+
+    /// Decodes a proprietary payload, reassembled first for fast packet
+    /// PGNs. Fails with `NotImplemented` for PGNs outside the proprietary
+    /// ranges.
+    pub fn decode(&self, id: &NmeaId, payload: &[u8]) -> Result<ProprietaryMessage<T>, NmeaError> {
+        let pgn = id.get_raw_pgn();
+        if !is_proprietary(pgn) {
+            return Err(NmeaError::NotImplemented);
+        }
+        if payload.len() < 2 {
+            return Err(NmeaError::ParseError);
+        }
+        let proprietary_id = ProprietaryId::from_raw(u16::from_le_bytes([payload[0], payload[1]]));
+
+        let decoded = match self.decoders.get(&(proprietary_id.manufacturer_code, pgn)) {
+            Some(decoder) => match decoder(payload) {
+                Ok(message) => Some(message),
+                Err(NmeaError::NotImplemented) => None,
+                Err(error) => return Err(error),
+            },
+            None => None,
+        };
+        let payload = match decoded {
+            Some(message) => ProprietaryPayload::Decoded(message),
+            None => ProprietaryPayload::Raw(payload.to_vec()),
+        };
+        Ok(ProprietaryMessage {
+            pgn,
+            id: proprietary_id,
+            payload,
+        })
+    }
+}
+
+impl<T> Default for ProprietaryDecoders<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Decoded {
+        Counter(u8),
+    }
+
+    fn decoders() -> ProprietaryDecoders<Decoded> {
+        let mut decoders = ProprietaryDecoders::new();
+        decoders.register(
+            Manufacturer::VictronEnergy as u16,
+            61184,
+            |payload: &[u8]| match payload.get(2) {
+                Some(0x01) => Ok(Decoded::Counter(payload[3])),
+                Some(_) => Err(NmeaError::NotImplemented),
+                None => Err(NmeaError::ParseError),
+            },
+        );
+        decoders
+    }
+
+    #[test]
+    fn reads_manufacturer_and_industry() {
+        let id = ProprietaryId::from_raw(u16::from_le_bytes([0x3B, 0x9F]));
+        assert_eq!(Some(Manufacturer::Raymarine), id.get_manufacturer());
+        assert_eq!(Some(IndustryCode::Marine), id.get_industry());
+        assert_eq!(
+            0x9F3B,
+            ProprietaryId::marine(Manufacturer::Raymarine).to_raw()
+        );
+    }
+
+    #[test]
+    fn dispatches_to_registered_decoder() {
+        // PGN 61184 from address 0x23 to 0x42
+        let id = NmeaId(0x18EF4223);
+        let victron = (ProprietaryId::marine(Manufacturer::VictronEnergy).to_raw()).to_le_bytes();
+
+        let message = decoders()
+            .decode(&id, &[victron[0], victron[1], 0x01, 7])
+            .unwrap();
+        assert_eq!(61184, message.pgn);
+        assert_eq!(
+            ProprietaryPayload::Decoded(Decoded::Counter(7)),
+            message.payload
+        );
+
+        let message = decoders()
+            .decode(&id, &[victron[0], victron[1], 0x02, 7])
+            .unwrap();
+        assert_eq!(
+            ProprietaryPayload::Raw(vec![victron[0], victron[1], 0x02, 7]),
+            message.payload
+        );
+
+        let garmin = ProprietaryId::marine(Manufacturer::Garmin)
+            .to_raw()
+            .to_le_bytes();
+        let message = decoders().decode(&id, &garmin).unwrap();
+        assert_eq!(Some(Manufacturer::Garmin), message.id.get_manufacturer());
+        assert!(matches!(message.payload, ProprietaryPayload::Raw(_)));
+
+        // Vessel heading is not proprietary
+        assert!(decoders().decode(&NmeaId(0x09F11200), &garmin).is_err());
+    }
+}
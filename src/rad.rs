@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub struct Rad {
     value: f32,
 }
@@ -15,7 +15,18 @@ impl Rad {
             value: f32::from(value),
         }
     }
+    /// Rounded to the 0.0001 rad resolution of angle fields.
+    pub fn from_radians(radians: f32) -> Self {
+        Rad {
+            value: (radians / 0.0001).round(),
+        }
+    }
+
     pub fn get_radians(&self) -> f32 {
         self.value * 0.0001
     }
+
+    pub fn get_degrees(&self) -> f32 {
+        self.get_radians().to_degrees()
+    }
 }
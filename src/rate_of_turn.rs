@@ -0,0 +1,37 @@
+use crate::{
+    field_value::FieldValue, nmea_frame::RateOfTurnFrame, pgn_fields::RateOfTurnFields,
+    units::AngularRate, Message, NmeaError,
+};
+
+/// PGN 127251
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct RateOfTurn {
+    pub sid: FieldValue<u8>,
+    /// Positive when turning to starboard.
+    pub rate: FieldValue<AngularRate>,
+}
+
+impl Message<RateOfTurn, RateOfTurnFrame> for RateOfTurn {
+    fn get_data(frame: RateOfTurnFrame) -> Result<RateOfTurn, NmeaError> {
+        let fields = RateOfTurnFields::get_data(&frame.data)?;
+        Ok(RateOfTurn {
+            sid: fields.sid.map(|sid| sid as u8),
+            rate: fields.rate.map(AngularRate::from_radians_per_second),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rate_of_turn() {
+        // -0.01 rad/s
+        let data = [1, 0x00, 0x1E, 0xFB, 0xFF, 0xFF, 0xFF, 0xFF];
+        let rate_of_turn = RateOfTurn::get_data(RateOfTurnFrame { data }).unwrap();
+        let rate = rate_of_turn.rate.value().unwrap();
+        assert_eq!(-0.01, rate.get_radians_per_second());
+        assert_eq!(-34.4, (rate.get_degrees_per_minute() * 10.0).round() / 10.0);
+    }
+}
@@ -0,0 +1,349 @@
+//! Raymarine SeaTalkNG autopilot messages.
+//!
+//! The pilot reports its mode on PGN 65379 and its locked heading on PGN
+//! 65360. It is driven either by SeaTalk1 keystrokes wrapped in PGN 126720,
+//! as the pilot controller sends them, or by group function commands that
+//! write the mode and heading fields directly.
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use crate::{
+    field_value::FieldValue,
+    group_function::{FieldPair, GroupFunction},
+    nmea_frame::{SeatalkPilotLockedHeadingFrame, SeatalkPilotModeFrame},
+    pgn::PGN,
+    pgn_fields::{SeatalkPilotLockedHeadingFields, SeatalkPilotModeFields},
+    proprietary::{IndustryCode, Manufacturer, ProprietaryDecoders, ProprietaryId},
+    rad::Rad,
+    write::FieldWriter,
+    Encode, Message, NmeaError,
+};
+
+/// Proprietary message id of SeaTalk1 datagrams carried in PGN 126720.
+const SEATALK1_ID: u16 = 0x81F0;
+/// SeaTalk1 keystroke datagram.
+const SEATALK1_KEYSTROKE: u8 = 0x86;
+/// Device id the pilot controller sends its keystrokes as.
+const KEYSTROKE_DEVICE: u8 = 0x21;
+/// Trailer pilot controllers send after every keystroke.
+const KEYSTROKE_TRAILER: [u8; 9] = [0xC1, 0xC2, 0xCD, 0x66, 0x80, 0xD3, 0x42, 0xB1, 0xC8];
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum PilotMode {
+    Standby = 0x0000,
+    Auto = 0x0040,
+    Wind = 0x0100,
+    Track = 0x0180,
+}
+
+/// Pilot controller keys, and the key combinations that select a mode.
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum PilotKey {
+    Auto = 0x01,
+    Standby = 0x02,
+    Track = 0x03,
+    Display = 0x04,
+    MinusOne = 0x05,
+    MinusTen = 0x06,
+    PlusOne = 0x07,
+    PlusTen = 0x08,
+    MinusOneAndMinusTen = 0x21,
+    PlusOneAndPlusTen = 0x22,
+    /// Standby and Auto pressed together.
+    Wind = 0x23,
+}
+
+/// PGN 126720 carrying a SeaTalk1 keystroke, a fast packet message.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SeatalkKeystroke {
+    pub key: Option<PilotKey>,
+}
+
+impl Message<SeatalkKeystroke, &[u8]> for SeatalkKeystroke {
+    fn get_data(data: &[u8]) -> Result<SeatalkKeystroke, NmeaError> {
+        if data.len() < 8 {
+            return Err(NmeaError::ParseError);
+        }
+        let datagram = u16::from_le_bytes([data[2], data[3]]);
+        if datagram != SEATALK1_ID || data[4] != SEATALK1_KEYSTROKE {
+            return Err(NmeaError::NotImplemented);
+        }
+        // The key code is repeated inverted as a check
+        if data[6] != !data[7] {
+            return Err(NmeaError::ParseError);
+        }
+        Ok(SeatalkKeystroke {
+            key: FromPrimitive::from_u8(data[6]),
+        })
+    }
+}
+
+impl Encode<Vec<u8>> for SeatalkKeystroke {
+    fn encode(&self) -> Vec<u8> {
+        let key = self.key.map_or(0xFF, |key| key as u8);
+        let mut writer = FieldWriter::new();
+        writer.put_u16(ProprietaryId::marine(Manufacturer::Raymarine).to_raw());
+        writer.put_u16(SEATALK1_ID);
+        writer.put_u8(SEATALK1_KEYSTROKE);
+        writer.put_u8(KEYSTROKE_DEVICE);
+        writer.put_u8(key);
+        writer.put_u8(!key);
+        for _ in 0..5 {
+            writer.put_u8(0xFF);
+        }
+        for byte in KEYSTROKE_TRAILER {
+            writer.put_u8(byte);
+        }
+        writer.into_payload()
+    }
+}
+
+/// PGN 65379
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SeatalkPilotMode {
+    pub mode: Option<PilotMode>,
+    /// Mode and sub mode as sent, for modes without a `PilotMode`.
+    pub raw_mode: u16,
+    pub mode_data: FieldValue<u16>,
+}
+
+impl Message<SeatalkPilotMode, &[u8]> for SeatalkPilotMode {
+    fn get_data(data: &[u8]) -> Result<SeatalkPilotMode, NmeaError> {
+        let fields = SeatalkPilotModeFields::get_data(data)?;
+        let raw_mode = fields
+            .pilot_mode
+            .value()
+            .map_or(u16::MAX, |mode| mode as u16);
+        Ok(SeatalkPilotMode {
+            mode: FromPrimitive::from_u16(raw_mode),
+            raw_mode,
+            mode_data: fields.pilot_mode_data.map(|data| data as u16),
+        })
+    }
+}
+
+impl Encode<SeatalkPilotModeFrame> for SeatalkPilotMode {
+    fn encode(&self) -> SeatalkPilotModeFrame {
+        SeatalkPilotModeFields {
+            manufacturer_code: raymarine_code(),
+            industry_code: marine_industry(),
+            pilot_mode: FieldValue::Value(f64::from(
+                self.mode.map_or(self.raw_mode, |mode| mode as u16),
+            )),
+            pilot_mode_data: self.mode_data.map(f64::from),
+        }
+        .to_frame()
+    }
+}
+
+/// PGN 65360
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SeatalkPilotLockedHeading {
+    pub sid: FieldValue<u8>,
+    pub target_heading_true: FieldValue<Rad>,
+    pub target_heading_magnetic: FieldValue<Rad>,
+}
+
+impl Message<SeatalkPilotLockedHeading, &[u8]> for SeatalkPilotLockedHeading {
+    fn get_data(data: &[u8]) -> Result<SeatalkPilotLockedHeading, NmeaError> {
+        let fields = SeatalkPilotLockedHeadingFields::get_data(data)?;
+        let heading =
+            |heading: FieldValue<f64>| heading.map(|heading| Rad::from_radians(heading as f32));
+        Ok(SeatalkPilotLockedHeading {
+            sid: fields.sid.map(|sid| sid as u8),
+            target_heading_true: heading(fields.target_heading_true),
+            target_heading_magnetic: heading(fields.target_heading_magnetic),
+        })
+    }
+}
+
+impl Encode<SeatalkPilotLockedHeadingFrame> for SeatalkPilotLockedHeading {
+    fn encode(&self) -> SeatalkPilotLockedHeadingFrame {
+        let heading =
+            |heading: FieldValue<Rad>| heading.map(|heading| f64::from(heading.get_radians()));
+        SeatalkPilotLockedHeadingFields {
+            manufacturer_code: raymarine_code(),
+            industry_code: marine_industry(),
+            sid: self.sid.map(f64::from),
+            target_heading_true: heading(self.target_heading_true),
+            target_heading_magnetic: heading(self.target_heading_magnetic),
+        }
+        .to_frame()
+    }
+}
+
+fn raymarine_code() -> FieldValue<f64> {
+    FieldValue::Value(f64::from(Manufacturer::Raymarine as u16))
+}
+
+fn marine_industry() -> FieldValue<f64> {
+    FieldValue::Value(f64::from(IndustryCode::Marine as u8))
+}
+
+/// The fields that tell the pilot a group function command is meant for a
+/// Raymarine PGN.
+fn raymarine_fields(pgn: u32) -> Result<Vec<FieldPair>, NmeaError> {
+    Ok(vec![
+        FieldPair::new(pgn, 1, u64::from(Manufacturer::Raymarine as u16))?,
+        FieldPair::new(pgn, 3, IndustryCode::Marine as u64)?,
+    ])
+}
+
+/// A group function command, to be sent to the pilot, that switches it to
+/// `mode`.
+pub fn set_pilot_mode(mode: PilotMode) -> Result<GroupFunction, NmeaError> {
+    let pgn = PGN::SeatalkPilotMode as u32;
+    let mut parameters = raymarine_fields(pgn)?;
+    parameters.push(FieldPair::new(pgn, 4, mode as u64)?);
+    parameters.push(FieldPair::new(pgn, 5, 0xFFFF)?);
+    Ok(GroupFunction::Command {
+        pgn,
+        priority: None,
+        parameters,
+    })
+}
+
+/// A group function command, to be sent to the pilot, that locks it onto a
+/// magnetic heading while in auto.
+pub fn set_locked_heading(heading: Rad) -> Result<GroupFunction, NmeaError> {
+    let pgn = PGN::SeatalkPilotLockedHeading as u32;
+    let mut parameters = raymarine_fields(pgn)?;
+    parameters.push(FieldPair::from_scaled(
+        pgn,
+        6,
+        f64::from(heading.get_radians()),
+    )?);
+    Ok(GroupFunction::Command {
+        pgn,
+        priority: None,
+        parameters,
+    })
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum RaymarineMessage {
+    Keystroke(SeatalkKeystroke),
+    PilotMode(SeatalkPilotMode),
+    PilotLockedHeading(SeatalkPilotLockedHeading),
+}
+
+/// Registers the Raymarine decoders, with `wrap` turning their messages into
+/// the caller's type.
+pub fn register<T: 'static>(
+    decoders: &mut ProprietaryDecoders<T>,
+    wrap: fn(RaymarineMessage) -> T,
+) {
+    let raymarine = Manufacturer::Raymarine as u16;
+    decoders.register(
+        raymarine,
+        PGN::ManufacturerProprietaryFastPacketAddressed as u32,
+        move |data: &[u8]| {
+            SeatalkKeystroke::get_data(data)
+                .map(|keystroke| wrap(RaymarineMessage::Keystroke(keystroke)))
+        },
+    );
+    decoders.register(
+        raymarine,
+        PGN::SeatalkPilotMode as u32,
+        move |data: &[u8]| {
+            SeatalkPilotMode::get_data(data).map(|mode| wrap(RaymarineMessage::PilotMode(mode)))
+        },
+    );
+    decoders.register(
+        raymarine,
+        PGN::SeatalkPilotLockedHeading as u32,
+        move |data: &[u8]| {
+            SeatalkPilotLockedHeading::get_data(data)
+                .map(|heading| wrap(RaymarineMessage::PilotLockedHeading(heading)))
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{proprietary::ProprietaryPayload, NmeaId};
+
+    #[test]
+    fn encodes_plus_ten_keystroke() {
+        let payload = SeatalkKeystroke {
+            key: Some(PilotKey::PlusTen),
+        }
+        .encode();
+        assert_eq!(
+            vec![
+                0x3B, 0x9F, 0xF0, 0x81, 0x86, 0x21, 0x08, 0xF7, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xC1,
+                0xC2, 0xCD, 0x66, 0x80, 0xD3, 0x42, 0xB1, 0xC8
+            ],
+            payload
+        );
+        assert_eq!(
+            Some(PilotKey::PlusTen),
+            SeatalkKeystroke::get_data(&payload).unwrap().key
+        );
+    }
+
+    #[test]
+    fn builds_mode_and_heading_commands() {
+        let payload = set_pilot_mode(PilotMode::Auto).unwrap().encode();
+        assert_eq!(
+            vec![
+                0x01, 0x63, 0xFF, 0x00, 0xF8, 0x04, 0x01, 0x3B, 0x07, 0x03, 0x04, 0x04, 0x40, 0x00,
+                0x05, 0xFF, 0xFF
+            ],
+            payload
+        );
+
+        // 90 degrees
+        let payload = set_locked_heading(Rad::new(15708)).unwrap().encode();
+        assert_eq!(
+            vec![
+                0x01, 0x50, 0xFF, 0x00, 0xF8, 0x03, 0x01, 0x3B, 0x07, 0x03, 0x04, 0x06, 0x5C, 0x3D
+            ],
+            payload
+        );
+    }
+
+    #[test]
+    fn decodes_pilot_status_through_proprietary_dispatch() {
+        let mut decoders = ProprietaryDecoders::new();
+        register(&mut decoders, |message| message);
+
+        // PGN 65379 from address 0xCC
+        let mode = SeatalkPilotMode {
+            mode: Some(PilotMode::Wind),
+            raw_mode: 0x0100,
+            mode_data: FieldValue::NotAvailable,
+        };
+        let data = [0x3B, 0x9F, 0x00, 0x01, 0xFF, 0xFF, 0xFF, 0xFF];
+        assert_eq!(data, mode.encode().data);
+        let message = decoders.decode(&NmeaId(0x08FF63CC), &data).unwrap();
+        assert_eq!(
+            ProprietaryPayload::Decoded(RaymarineMessage::PilotMode(mode)),
+            message.payload
+        );
+
+        // Mode data takes both bytes after the mode
+        let data = [0x3B, 0x9F, 0x40, 0x00, 0x34, 0x12, 0xFF, 0xFF];
+        let mode = SeatalkPilotMode::get_data(&data).unwrap();
+        assert_eq!(Some(PilotMode::Auto), mode.mode);
+        assert_eq!(FieldValue::Value(0x1234), mode.mode_data);
+        assert_eq!(data, mode.encode().data);
+
+        // PGN 65360
+        let data = [0x3B, 0x9F, 0xFF, 0xFF, 0xFF, 0x5C, 0x3D, 0xFF];
+        let message = decoders.decode(&NmeaId(0x08FF50CC), &data).unwrap();
+        let ProprietaryPayload::Decoded(RaymarineMessage::PilotLockedHeading(heading)) =
+            message.payload
+        else {
+            panic!("expected a locked heading");
+        };
+        assert_eq!(FieldValue::NotAvailable, heading.target_heading_true);
+        assert_eq!(
+            FieldValue::Value(Rad::new(15708)),
+            heading.target_heading_magnetic
+        );
+        assert_eq!(data, heading.encode().data);
+    }
+}
@@ -0,0 +1,163 @@
+use nom::{multi::count, IResult};
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use crate::{
+    field_value::FieldValue,
+    parse::{take_byte, take_i32, take_lau_string, take_u16, BitInput},
+    units::{Latitude, Longitude},
+    Message, NmeaError,
+};
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum RouteDirection {
+    Forward = 0,
+    Reverse = 1,
+}
+
+/// One entry of the waypoint list in a route.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RouteWaypoint {
+    pub id: u16,
+    pub name: String,
+    pub latitude: FieldValue<Latitude>,
+    pub longitude: FieldValue<Longitude>,
+}
+
+/// PGN 129285, a fast packet message.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RouteWaypointInformation {
+    /// Position in the route of the first waypoint in `waypoints`.
+    pub start_rps: u16,
+    pub database_id: FieldValue<u16>,
+    pub route_id: FieldValue<u16>,
+    pub direction: Option<RouteDirection>,
+    pub supplementary_data_available: FieldValue<bool>,
+    pub route_name: String,
+    pub waypoints: Vec<RouteWaypoint>,
+}
+
+impl RouteWaypointInformation {
+    /// The waypoints still ahead when heading for the waypoint with
+    /// `destination_id`, as reported by PGN 129284.
+    pub fn upcoming_waypoints(&self, destination_id: u16) -> &[RouteWaypoint] {
+        match self
+            .waypoints
+            .iter()
+            .position(|waypoint| waypoint.id == destination_id)
+        {
+            Some(index) => &self.waypoints[index..],
+            None => &[],
+        }
+    }
+}
+
+fn parse_route_waypoint(i: BitInput) -> IResult<BitInput, RouteWaypoint> {
+    let (i, id) = take_u16(i)?;
+    let (i, name) = take_lau_string(i)?;
+    let (i, latitude) = take_i32(i)?;
+    let (i, longitude) = take_i32(i)?;
+
+    let waypoint = RouteWaypoint {
+        id,
+        name,
+        latitude: FieldValue::from_i32(latitude).map(Latitude::new),
+        longitude: FieldValue::from_i32(longitude).map(Longitude::new),
+    };
+    Ok((i, waypoint))
+}
+
+fn parse_route_waypoint_information(i: BitInput) -> IResult<BitInput, RouteWaypointInformation> {
+    let (i, start_rps) = take_u16(i)?;
+    let (i, item_count) = take_u16(i)?;
+    let (i, database_id) = take_u16(i)?;
+    let (i, route_id) = take_u16(i)?;
+    let (i, direction_and_supplementary) = take_byte(i)?;
+    let (i, route_name) = take_lau_string(i)?;
+    let (i, _) = take_byte(i)?;
+    let (i, waypoints) = count(parse_route_waypoint, usize::from(item_count))(i)?;
+
+    let route = RouteWaypointInformation {
+        start_rps,
+        database_id: FieldValue::from_u16(database_id),
+        route_id: FieldValue::from_u16(route_id),
+        direction: FromPrimitive::from_u8(direction_and_supplementary & 0x03),
+        supplementary_data_available: FieldValue::from_two_bits(
+            (direction_and_supplementary >> 2) & 0x03,
+        ),
+        route_name,
+        waypoints,
+    };
+    Ok((i, route))
+}
+
+impl Message<RouteWaypointInformation, &[u8]> for RouteWaypointInformation {
+    fn get_data(data: &[u8]) -> Result<RouteWaypointInformation, NmeaError> {
+        let parse_result: IResult<&[u8], RouteWaypointInformation> =
+            nom::bits::bits(parse_route_waypoint_information)(data);
+        match parse_result {
+            Ok((_, route)) => Ok(route),
+            Err(_e) => Err(NmeaError::ParseError),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::write::FieldWriter;
+
+    fn put_lau_string(writer: &mut FieldWriter, text: &[u8]) {
+        writer.put_u8(text.len() as u8 + 2);
+        writer.put_u8(1);
+        for byte in text {
+            writer.put_u8(*byte);
+        }
+    }
+
+    #[test]
+    fn parses_route_waypoint_information() {
+        let mut writer = FieldWriter::new();
+        writer.put_u16(1);
+        writer.put_u16(2);
+        writer.put_u16(0);
+        writer.put_u16(7);
+        // Forward, no supplementary data
+        writer.put_u8(0xF0);
+        put_lau_string(&mut writer, b"IJMUIDEN");
+        writer.put_u8(0xFF);
+        writer.put_u16(11);
+        put_lau_string(&mut writer, b"IJ1");
+        writer.put_u32(524630000);
+        writer.put_u32(45500000);
+        writer.put_u16(12);
+        put_lau_string(&mut writer, b"HAVEN");
+        writer.put_u32(524650000);
+        writer.put_u32(46000000);
+        let payload = writer.into_payload();
+
+        let route = RouteWaypointInformation::get_data(&payload).unwrap();
+        assert_eq!(1, route.start_rps);
+        assert_eq!(FieldValue::Value(7), route.route_id);
+        assert_eq!(Some(RouteDirection::Forward), route.direction);
+        assert_eq!(FieldValue::Value(false), route.supplementary_data_available);
+        assert_eq!("IJMUIDEN", route.route_name);
+        assert_eq!(2, route.waypoints.len());
+        assert_eq!("HAVEN", route.waypoints[1].name);
+        assert_eq!(
+            52.465,
+            route.waypoints[1].latitude.value().unwrap().get_degrees()
+        );
+
+        let upcoming = route.upcoming_waypoints(12);
+        assert_eq!(1, upcoming.len());
+        assert_eq!(12, upcoming[0].id);
+        assert!(route.upcoming_waypoints(99).is_empty());
+    }
+
+    #[test]
+    fn rejects_truncated_waypoint_list() {
+        let data = [0, 0, 3, 0, 0, 0, 1, 0, 0xF0, 2, 1, 0xFF];
+        assert!(RouteWaypointInformation::get_data(&data).is_err());
+    }
+}
@@ -0,0 +1,66 @@
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use crate::{
+    field_value::FieldValue, nmea_frame::SpeedFrame, pgn_fields::SpeedFields, units::Speed,
+    Message, NmeaError,
+};
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum SpeedSensorType {
+    PaddleWheel = 0,
+    PitotTube = 1,
+    Doppler = 2,
+    Correlation = 3,
+    ElectroMagnetic = 4,
+}
+
+/// PGN 128259, speed through the water and, from sensors able to measure
+/// it, speed over the ground.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SpeedWaterReferenced {
+    pub sid: FieldValue<u8>,
+    pub water_referenced: FieldValue<Speed>,
+    pub ground_referenced: FieldValue<Speed>,
+    pub sensor_type: Option<SpeedSensorType>,
+}
+
+impl Message<SpeedWaterReferenced, SpeedFrame> for SpeedWaterReferenced {
+    fn get_data(frame: SpeedFrame) -> Result<SpeedWaterReferenced, NmeaError> {
+        let fields = SpeedFields::get_data(&frame.data)?;
+        Ok(SpeedWaterReferenced {
+            sid: fields.sid.map(|sid| sid as u8),
+            water_referenced: fields
+                .speed_water_referenced
+                .map(|speed| Speed::from_meters_per_second(speed as f32)),
+            ground_referenced: fields
+                .speed_ground_referenced
+                .map(|speed| Speed::from_meters_per_second(speed as f32)),
+            sensor_type: fields
+                .speed_water_referenced_type
+                .value()
+                .and_then(FromPrimitive::from_f64),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_speed() {
+        let data = [0, 0x2C, 0x01, 0xFF, 0xFF, 0, 0xFF, 0xFF];
+        let speed = SpeedWaterReferenced::get_data(SpeedFrame { data }).unwrap();
+        assert_eq!(
+            3.0,
+            speed
+                .water_referenced
+                .value()
+                .unwrap()
+                .get_meters_per_second()
+        );
+        assert_eq!(FieldValue::NotAvailable, speed.ground_referenced);
+        assert_eq!(Some(SpeedSensorType::PaddleWheel), speed.sensor_type);
+    }
+}
@@ -0,0 +1,466 @@
+use core::time::Duration;
+
+use nom::sequence::pair;
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use crate::{
+    cross_track_error::XTEMode,
+    date::{Date, DateTime, Time},
+    field_value::FieldValue,
+    parse::{parse_at, take_fixed_string, take_lau_string},
+    pgn_fields::{
+        CurrentStationDataFields, MeteorologicalStationDataFields, MooredBuoyStationDataFields,
+        SalinityStationDataFields, TideStationDataFields,
+    },
+    rad::Rad,
+    units::{Distance, Latitude, Longitude, Pressure, Speed, Temperature},
+    Message, NmeaError,
+};
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum TideTendency {
+    Falling = 0,
+    Rising = 1,
+}
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum WindReference {
+    TrueNorth = 0,
+    Magnetic = 1,
+    Apparent = 2,
+    TrueBoat = 3,
+    TrueWater = 4,
+}
+
+fn measured_at(date: FieldValue<Date>, time: FieldValue<Time>) -> Option<DateTime> {
+    Some(DateTime {
+        date: date.value()?,
+        time: time.value()?,
+    })
+}
+
+fn date(days: FieldValue<f64>) -> FieldValue<Date> {
+    days.map(|days| Date::new(days as u16))
+}
+
+fn speed(speed: FieldValue<f64>) -> FieldValue<Speed> {
+    speed.map(|speed| Speed::from_meters_per_second(speed as f32))
+}
+
+fn direction(radians: FieldValue<f64>) -> FieldValue<Rad> {
+    radians.map(|radians| Rad::from_radians(radians as f32))
+}
+
+fn temperature(kelvin: FieldValue<f64>) -> FieldValue<Temperature> {
+    kelvin.map(|kelvin| Temperature::from_kelvin(kelvin as f32))
+}
+
+fn pressure(pascals: FieldValue<f64>) -> FieldValue<Pressure> {
+    pascals.map(|pascals| Pressure::from_pascals(pascals as f32))
+}
+
+fn mode(mode: FieldValue<f64>) -> Option<XTEMode> {
+    mode.value().and_then(FromPrimitive::from_f64)
+}
+
+/// PGN 130320, a fast packet message.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TideStationData {
+    pub mode: Option<XTEMode>,
+    pub tide_tendency: Option<TideTendency>,
+    pub date: FieldValue<Date>,
+    pub time: FieldValue<Time>,
+    pub latitude: FieldValue<Latitude>,
+    pub longitude: FieldValue<Longitude>,
+    /// Relative to the station's chart datum.
+    pub tide_level: FieldValue<Distance>,
+    pub tide_level_standard_deviation: FieldValue<Distance>,
+    pub station_id: String,
+    pub station_name: String,
+}
+
+impl TideStationData {
+    pub fn get_date_time(&self) -> Option<DateTime> {
+        measured_at(self.date, self.time)
+    }
+}
+
+impl Message<TideStationData, &[u8]> for TideStationData {
+    fn get_data(data: &[u8]) -> Result<TideStationData, NmeaError> {
+        let fields = TideStationDataFields::get_data(data)?;
+        let (station_id, station_name) =
+            parse_at(data, 19, pair(take_lau_string, take_lau_string))?;
+        Ok(TideStationData {
+            mode: mode(fields.mode),
+            tide_tendency: fields
+                .tide_tendency
+                .value()
+                .and_then(FromPrimitive::from_f64),
+            date: date(fields.measurement_date),
+            time: fields.measurement_time.map(Time::from_seconds),
+            latitude: fields.station_latitude.map(Latitude::from_degrees),
+            longitude: fields.station_longitude.map(Longitude::from_degrees),
+            tide_level: fields.tide_level.map(Distance::from_meters),
+            tide_level_standard_deviation: fields
+                .tide_level_standard_deviation
+                .map(Distance::from_meters),
+            station_id,
+            station_name,
+        })
+    }
+}
+
+/// PGN 130321, a fast packet message.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SalinityStationData {
+    pub mode: Option<XTEMode>,
+    pub date: FieldValue<Date>,
+    pub time: FieldValue<Time>,
+    pub latitude: FieldValue<Latitude>,
+    pub longitude: FieldValue<Longitude>,
+    /// Parts per thousand
+    pub salinity: FieldValue<f32>,
+    pub water_temperature: FieldValue<Temperature>,
+    pub station_id: String,
+    pub station_name: String,
+}
+
+impl SalinityStationData {
+    pub fn get_date_time(&self) -> Option<DateTime> {
+        measured_at(self.date, self.time)
+    }
+}
+
+impl Message<SalinityStationData, &[u8]> for SalinityStationData {
+    fn get_data(data: &[u8]) -> Result<SalinityStationData, NmeaError> {
+        let fields = SalinityStationDataFields::get_data(data)?;
+        let (station_id, station_name) =
+            parse_at(data, 21, pair(take_lau_string, take_lau_string))?;
+        Ok(SalinityStationData {
+            mode: mode(fields.mode),
+            date: date(fields.measurement_date),
+            time: fields.measurement_time.map(Time::from_seconds),
+            latitude: fields.station_latitude.map(Latitude::from_degrees),
+            longitude: fields.station_longitude.map(Longitude::from_degrees),
+            // The layout reads the raw bits of a 32 bit float
+            salinity: fields
+                .salinity
+                .map(|salinity| f32::from_bits(salinity as u32)),
+            water_temperature: temperature(fields.water_temperature),
+            station_id,
+            station_name,
+        })
+    }
+}
+
+/// PGN 130322, a fast packet message.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CurrentStationData {
+    pub mode: Option<XTEMode>,
+    pub date: FieldValue<Date>,
+    pub time: FieldValue<Time>,
+    pub latitude: FieldValue<Latitude>,
+    pub longitude: FieldValue<Longitude>,
+    pub measurement_depth: FieldValue<Distance>,
+    pub current_speed: FieldValue<Speed>,
+    /// Direction the current flows towards.
+    pub current_flow_direction: FieldValue<Rad>,
+    pub water_temperature: FieldValue<Temperature>,
+    pub station_id: String,
+    pub station_name: String,
+}
+
+impl CurrentStationData {
+    pub fn get_date_time(&self) -> Option<DateTime> {
+        measured_at(self.date, self.time)
+    }
+}
+
+impl Message<CurrentStationData, &[u8]> for CurrentStationData {
+    fn get_data(data: &[u8]) -> Result<CurrentStationData, NmeaError> {
+        let fields = CurrentStationDataFields::get_data(data)?;
+        let (station_id, station_name) =
+            parse_at(data, 25, pair(take_lau_string, take_lau_string))?;
+        Ok(CurrentStationData {
+            mode: mode(fields.mode),
+            date: date(fields.measurement_date),
+            time: fields.measurement_time.map(Time::from_seconds),
+            latitude: fields.station_latitude.map(Latitude::from_degrees),
+            longitude: fields.station_longitude.map(Longitude::from_degrees),
+            measurement_depth: fields.measurement_depth.map(Distance::from_meters),
+            current_speed: speed(fields.current_speed),
+            current_flow_direction: direction(fields.current_flow_direction),
+            water_temperature: temperature(fields.water_temperature),
+            station_id,
+            station_name,
+        })
+    }
+}
+
+/// PGN 130323, a fast packet message.
+#[derive(Debug, PartialEq, Clone)]
+pub struct MeteorologicalStationData {
+    pub mode: Option<XTEMode>,
+    pub date: FieldValue<Date>,
+    pub time: FieldValue<Time>,
+    pub latitude: FieldValue<Latitude>,
+    pub longitude: FieldValue<Longitude>,
+    pub wind_speed: FieldValue<Speed>,
+    pub wind_direction: FieldValue<Rad>,
+    pub wind_reference: Option<WindReference>,
+    pub wind_gusts: FieldValue<Speed>,
+    pub atmospheric_pressure: FieldValue<Pressure>,
+    pub ambient_temperature: FieldValue<Temperature>,
+    pub station_id: String,
+    pub station_name: String,
+}
+
+impl MeteorologicalStationData {
+    pub fn get_date_time(&self) -> Option<DateTime> {
+        measured_at(self.date, self.time)
+    }
+}
+
+impl Message<MeteorologicalStationData, &[u8]> for MeteorologicalStationData {
+    fn get_data(data: &[u8]) -> Result<MeteorologicalStationData, NmeaError> {
+        let fields = MeteorologicalStationDataFields::get_data(data)?;
+        let (station_id, station_name) =
+            parse_at(data, 26, pair(take_lau_string, take_lau_string))?;
+        Ok(MeteorologicalStationData {
+            mode: mode(fields.mode),
+            date: date(fields.measurement_date),
+            time: fields.measurement_time.map(Time::from_seconds),
+            latitude: fields.station_latitude.map(Latitude::from_degrees),
+            longitude: fields.station_longitude.map(Longitude::from_degrees),
+            wind_speed: speed(fields.wind_speed),
+            wind_direction: direction(fields.wind_direction),
+            wind_reference: fields
+                .wind_reference
+                .value()
+                .and_then(FromPrimitive::from_f64),
+            wind_gusts: speed(fields.wind_gusts),
+            atmospheric_pressure: pressure(fields.atmospheric_pressure),
+            ambient_temperature: temperature(fields.ambient_temperature),
+            station_id,
+            station_name,
+        })
+    }
+}
+
+/// PGN 130324, a fast packet message.
+#[derive(Debug, PartialEq, Clone)]
+pub struct MooredBuoyStationData {
+    pub mode: Option<XTEMode>,
+    pub date: FieldValue<Date>,
+    pub time: FieldValue<Time>,
+    pub latitude: FieldValue<Latitude>,
+    pub longitude: FieldValue<Longitude>,
+    pub wind_speed: FieldValue<Speed>,
+    pub wind_direction: FieldValue<Rad>,
+    pub wind_reference: Option<WindReference>,
+    pub wind_gusts: FieldValue<Speed>,
+    pub wave_height: FieldValue<Distance>,
+    pub dominant_wave_period: FieldValue<Duration>,
+    pub atmospheric_pressure: FieldValue<Pressure>,
+    /// Raw value as sent.
+    pub pressure_tendency_rate: FieldValue<u16>,
+    pub air_temperature: FieldValue<Temperature>,
+    pub water_temperature: FieldValue<Temperature>,
+    /// Fixed 8 character identifier, such as a WMO buoy number.
+    pub station_id: String,
+}
+
+impl MooredBuoyStationData {
+    pub fn get_date_time(&self) -> Option<DateTime> {
+        measured_at(self.date, self.time)
+    }
+}
+
+impl Message<MooredBuoyStationData, &[u8]> for MooredBuoyStationData {
+    fn get_data(data: &[u8]) -> Result<MooredBuoyStationData, NmeaError> {
+        let fields = MooredBuoyStationDataFields::get_data(data)?;
+        Ok(MooredBuoyStationData {
+            mode: mode(fields.mode),
+            date: date(fields.measurement_date),
+            time: fields.measurement_time.map(Time::from_seconds),
+            latitude: fields.station_latitude.map(Latitude::from_degrees),
+            longitude: fields.station_longitude.map(Longitude::from_degrees),
+            wind_speed: speed(fields.wind_speed),
+            wind_direction: direction(fields.wind_direction),
+            wind_reference: fields
+                .wind_reference
+                .value()
+                .and_then(FromPrimitive::from_f64),
+            wind_gusts: speed(fields.wind_gusts),
+            wave_height: fields.wave_height.map(Distance::from_meters),
+            dominant_wave_period: fields
+                .dominant_wave_period
+                .map(|seconds| Duration::from_secs(seconds as u64)),
+            atmospheric_pressure: pressure(fields.atmospheric_pressure),
+            pressure_tendency_rate: fields.pressure_tendency_rate.map(|rate| rate as u16),
+            air_temperature: temperature(fields.air_temperature),
+            water_temperature: temperature(fields.water_temperature),
+            station_id: parse_at(data, 34, take_fixed_string(8))?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tide_and_salinity_payloads() {
+        let tide = [
+            0xC0, 0x38, 0x4A, 0x00, 0x51, 0x25, 0x02, 0x78, 0x5F, 0x42, 0x18, 0x38, 0x58, 0xE2,
+            0xD3, 0xC0, 0xFE, 0x0C, 0x00, 0x09, 0x01, 0x38, 0x35, 0x31, 0x38, 0x37, 0x35, 0x30,
+            0x04, 0x01, 0x4E, 0x59,
+        ];
+        let tide = TideStationData::get_data(&tide).unwrap();
+        assert_eq!(Some(XTEMode::Autonomous), tide.mode);
+        assert_eq!(Some(TideTendency::Falling), tide.tide_tendency);
+        let date_time = tide.get_date_time().unwrap();
+        assert_eq!(19000, date_time.date.get_days_since_epoch());
+        assert_eq!(3600, date_time.time.get_seconds());
+        assert_eq!(40.7003, tide.latitude.value().unwrap().get_degrees());
+        assert_eq!(-74.0141, tide.longitude.value().unwrap().get_degrees());
+        assert_eq!(-0.32, tide.tide_level.value().unwrap().get_meters());
+        assert_eq!(
+            0.12,
+            tide.tide_level_standard_deviation
+                .value()
+                .unwrap()
+                .get_meters()
+        );
+        assert_eq!("8518750", tide.station_id);
+        assert_eq!("NY", tide.station_name);
+
+        let salinity = [
+            0xF1, 0x38, 0x4A, 0x00, 0x51, 0x25, 0x02, 0x78, 0x5F, 0x42, 0x18, 0x38, 0x58, 0xE2,
+            0xD3, 0x00, 0x00, 0x05, 0x42, 0x83, 0x72, 0x05, 0x01, 0x53, 0x41, 0x4C, 0x06, 0x01,
+            0x50, 0x69, 0x65, 0x72,
+        ];
+        let salinity = SalinityStationData::get_data(&salinity).unwrap();
+        assert_eq!(Some(XTEMode::Differential), salinity.mode);
+        assert_eq!(FieldValue::Value(33.25), salinity.salinity);
+        assert_eq!(
+            20.0,
+            salinity
+                .water_temperature
+                .value()
+                .unwrap()
+                .get_celsius()
+                .round()
+        );
+        assert_eq!("SAL", salinity.station_id);
+        assert_eq!("Pier", salinity.station_name);
+    }
+
+    #[test]
+    fn parses_current_station_payload() {
+        let data = [
+            0xF0, 0x38, 0x4A, 0x00, 0x51, 0x25, 0x02, 0x78, 0x5F, 0x42, 0x18, 0x38, 0x58, 0xE2,
+            0xD3, 0xFA, 0x00, 0x00, 0x00, 0x4B, 0x00, 0x5C, 0x3D, 0xFF, 0xFF, 0x04, 0x01, 0x43,
+            0x37, 0x06, 0x01, 0x47, 0x61, 0x74, 0x65,
+        ];
+        let current = CurrentStationData::get_data(&data).unwrap();
+        assert_eq!(Some(XTEMode::Autonomous), current.mode);
+        assert_eq!(2.5, current.measurement_depth.value().unwrap().get_meters());
+        assert_eq!(
+            0.75,
+            current
+                .current_speed
+                .value()
+                .unwrap()
+                .get_meters_per_second()
+        );
+        assert_eq!(
+            90.0,
+            current
+                .current_flow_direction
+                .value()
+                .unwrap()
+                .get_degrees()
+                .round()
+        );
+        assert_eq!(FieldValue::NotAvailable, current.water_temperature);
+        assert_eq!("C7", current.station_id);
+        assert_eq!("Gate", current.station_name);
+    }
+
+    #[test]
+    fn parses_meteorological_and_buoy_payloads() {
+        let meteo = [
+            0xF4, 0x38, 0x4A, 0x00, 0x51, 0x25, 0x02, 0x78, 0x5F, 0x42, 0x18, 0x38, 0x58, 0xE2,
+            0xD3, 0x08, 0x02, 0xB8, 0x7A, 0xF9, 0xFF, 0xFF, 0xE6, 0x03, 0x9B, 0x6E, 0x06, 0x01,
+            0x4B, 0x4E, 0x59, 0x43, 0x0E, 0x01, 0x43, 0x65, 0x6E, 0x74, 0x72, 0x61, 0x6C, 0x20,
+            0x50, 0x61, 0x72, 0x6B,
+        ];
+        let meteo = MeteorologicalStationData::get_data(&meteo).unwrap();
+        assert_eq!(Some(XTEMode::Manual), meteo.mode);
+        assert_eq!(
+            5.2,
+            meteo.wind_speed.value().unwrap().get_meters_per_second()
+        );
+        assert_eq!(
+            180.0,
+            meteo.wind_direction.value().unwrap().get_degrees().round()
+        );
+        assert_eq!(Some(WindReference::Magnetic), meteo.wind_reference);
+        assert_eq!(FieldValue::NotAvailable, meteo.wind_gusts);
+        assert_eq!(
+            99800.0,
+            meteo.atmospheric_pressure.value().unwrap().get_pascals()
+        );
+        assert_eq!(
+            10.0,
+            meteo
+                .ambient_temperature
+                .value()
+                .unwrap()
+                .get_celsius()
+                .round()
+        );
+        assert_eq!("KNYC", meteo.station_id);
+        assert_eq!("Central Park", meteo.station_name);
+
+        let data = [
+            0xF0, 0x38, 0x4A, 0x00, 0x51, 0x25, 0x02, 0x78, 0x5F, 0x42, 0x18, 0x38, 0x58, 0xE2,
+            0xD3, 0x2C, 0x01, 0x00, 0x00, 0xF8, 0xC2, 0x01, 0x7D, 0x00, 0x06, 0x00, 0xFC, 0x03,
+            0xFF, 0xFF, 0xFF, 0xFF, 0x8F, 0x70, 0x36, 0x32, 0x30, 0x38, 0x31, 0x40, 0x40, 0x40,
+        ];
+        let buoy = MooredBuoyStationData::get_data(&data).unwrap();
+        assert_eq!(
+            3.0,
+            buoy.wind_speed.value().unwrap().get_meters_per_second()
+        );
+        assert_eq!(Some(WindReference::TrueNorth), buoy.wind_reference);
+        assert_eq!(
+            4.5,
+            buoy.wind_gusts.value().unwrap().get_meters_per_second()
+        );
+        assert_eq!(1.25, buoy.wave_height.value().unwrap().get_meters());
+        assert_eq!(
+            FieldValue::Value(Duration::from_secs(6)),
+            buoy.dominant_wave_period
+        );
+        assert_eq!(
+            102000.0,
+            buoy.atmospheric_pressure.value().unwrap().get_pascals()
+        );
+        assert_eq!(FieldValue::NotAvailable, buoy.pressure_tendency_rate);
+        assert_eq!(FieldValue::NotAvailable, buoy.air_temperature);
+        assert_eq!(
+            15.0,
+            buoy.water_temperature
+                .value()
+                .unwrap()
+                .get_celsius()
+                .round()
+        );
+        assert_eq!("62081", buoy.station_id);
+
+        assert!(MooredBuoyStationData::get_data(&data[..20]).is_err());
+    }
+}
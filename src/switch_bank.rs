@@ -0,0 +1,202 @@
+use nom::IResult;
+
+use crate::{
+    nmea_frame::{BinarySwitchBankStatusFrame, SwitchBankControlFrame},
+    parse::{take_byte, take_bytes, BitInput},
+    write::FieldWriter,
+    Encode, Message, NmeaError,
+};
+
+/// Channels in one switch bank instance.
+pub const SWITCH_BANK_CHANNELS: usize = 28;
+
+/// Two bit switch indicator.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SwitchState {
+    Off,
+    On,
+    Error,
+    /// In control messages, leaves the channel as it is.
+    Unavailable,
+}
+
+impl SwitchState {
+    fn from_two_bits(raw: u8) -> SwitchState {
+        match raw & 0x03 {
+            0 => SwitchState::Off,
+            1 => SwitchState::On,
+            2 => SwitchState::Error,
+            _ => SwitchState::Unavailable,
+        }
+    }
+
+    fn to_two_bits(self) -> u8 {
+        match self {
+            SwitchState::Off => 0,
+            SwitchState::On => 1,
+            SwitchState::Error => 2,
+            SwitchState::Unavailable => 3,
+        }
+    }
+}
+
+fn parse_switch_bank(i: BitInput) -> IResult<BitInput, (u8, [SwitchState; SWITCH_BANK_CHANNELS])> {
+    let (i, instance) = take_byte(i)?;
+    let (i, raw) = take_bytes(7)(i)?;
+
+    let mut states = [SwitchState::Unavailable; SWITCH_BANK_CHANNELS];
+    for (channel, state) in states.iter_mut().enumerate() {
+        *state = SwitchState::from_two_bits(raw[channel / 4] >> (channel % 4 * 2));
+    }
+    Ok((i, (instance, states)))
+}
+
+fn put_switch_bank(
+    writer: &mut FieldWriter,
+    instance: u8,
+    states: &[SwitchState; SWITCH_BANK_CHANNELS],
+) {
+    writer.put_u8(instance);
+    for channels in states.chunks(4) {
+        let byte = channels.iter().enumerate().fold(0, |byte, (index, state)| {
+            byte | state.to_two_bits() << (index * 2)
+        });
+        writer.put_u8(byte);
+    }
+}
+
+/// PGN 127501
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BinarySwitchBankStatus {
+    pub instance: u8,
+    /// Channel 1 is at index 0.
+    pub indicators: [SwitchState; SWITCH_BANK_CHANNELS],
+}
+
+impl BinarySwitchBankStatus {
+    /// State of `channel`, numbered from 1 as on the panel.
+    pub fn get_channel(&self, channel: usize) -> Option<SwitchState> {
+        self.indicators.get(channel.checked_sub(1)?).copied()
+    }
+}
+
+impl Message<BinarySwitchBankStatus, BinarySwitchBankStatusFrame> for BinarySwitchBankStatus {
+    fn get_data(frame: BinarySwitchBankStatusFrame) -> Result<BinarySwitchBankStatus, NmeaError> {
+        let data = frame.data;
+        let parse_result: IResult<&[u8], (u8, [SwitchState; SWITCH_BANK_CHANNELS])> =
+            nom::bits::bits(parse_switch_bank)(&data);
+        match parse_result {
+            Ok((_, (instance, indicators))) => Ok(BinarySwitchBankStatus {
+                instance,
+                indicators,
+            }),
+            Err(_e) => Err(NmeaError::ParseError),
+        }
+    }
+}
+
+impl Encode<BinarySwitchBankStatusFrame> for BinarySwitchBankStatus {
+    fn encode(&self) -> BinarySwitchBankStatusFrame {
+        let mut writer = FieldWriter::new();
+        put_switch_bank(&mut writer, self.instance, &self.indicators);
+        BinarySwitchBankStatusFrame {
+            data: writer.into_frame(),
+        }
+    }
+}
+
+/// PGN 127502. Channels set to `Unavailable` are left unchanged.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SwitchBankControl {
+    pub instance: u8,
+    /// Channel 1 is at index 0.
+    pub switches: [SwitchState; SWITCH_BANK_CHANNELS],
+}
+
+impl SwitchBankControl {
+    /// Switches one channel, numbered from 1, on or off.
+    pub fn set_channel(instance: u8, channel: usize, on: bool) -> Option<SwitchBankControl> {
+        let mut switches = [SwitchState::Unavailable; SWITCH_BANK_CHANNELS];
+        *switches.get_mut(channel.checked_sub(1)?)? = if on {
+            SwitchState::On
+        } else {
+            SwitchState::Off
+        };
+        Some(SwitchBankControl { instance, switches })
+    }
+
+    /// Flips one channel of the bank `status` reports on. `None` when the
+    /// channel's current state is not known.
+    pub fn toggle(status: &BinarySwitchBankStatus, channel: usize) -> Option<SwitchBankControl> {
+        match status.get_channel(channel)? {
+            SwitchState::Off => Self::set_channel(status.instance, channel, true),
+            SwitchState::On => Self::set_channel(status.instance, channel, false),
+            _ => None,
+        }
+    }
+}
+
+impl Message<SwitchBankControl, SwitchBankControlFrame> for SwitchBankControl {
+    fn get_data(frame: SwitchBankControlFrame) -> Result<SwitchBankControl, NmeaError> {
+        let data = frame.data;
+        let parse_result: IResult<&[u8], (u8, [SwitchState; SWITCH_BANK_CHANNELS])> =
+            nom::bits::bits(parse_switch_bank)(&data);
+        match parse_result {
+            Ok((_, (instance, switches))) => Ok(SwitchBankControl { instance, switches }),
+            Err(_e) => Err(NmeaError::ParseError),
+        }
+    }
+}
+
+impl Encode<SwitchBankControlFrame> for SwitchBankControl {
+    fn encode(&self) -> SwitchBankControlFrame {
+        let mut writer = FieldWriter::new();
+        put_switch_bank(&mut writer, self.instance, &self.switches);
+        SwitchBankControlFrame {
+            data: writer.into_frame(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_switch_bank_status() {
+        // Channels 1 and 3 on, 2 off, 4 in error, 5 to 28 unavailable
+        let data = [0x02, 0x91, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+        let status =
+            BinarySwitchBankStatus::get_data(BinarySwitchBankStatusFrame { data }).unwrap();
+        assert_eq!(2, status.instance);
+        assert_eq!(Some(SwitchState::On), status.get_channel(1));
+        assert_eq!(Some(SwitchState::Off), status.get_channel(2));
+        assert_eq!(Some(SwitchState::On), status.get_channel(3));
+        assert_eq!(Some(SwitchState::Error), status.get_channel(4));
+        assert_eq!(Some(SwitchState::Unavailable), status.get_channel(28));
+        assert_eq!(None, status.get_channel(0));
+        assert_eq!(None, status.get_channel(29));
+        assert_eq!(data, status.encode().data);
+    }
+
+    #[test]
+    fn toggles_one_channel() {
+        let data = [0x02, 0x91, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+        let status =
+            BinarySwitchBankStatus::get_data(BinarySwitchBankStatusFrame { data }).unwrap();
+
+        let control = SwitchBankControl::toggle(&status, 2).unwrap();
+        assert_eq!(
+            [0x02, 0xF7, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+            control.encode().data
+        );
+        let control = SwitchBankControl::toggle(&status, 3).unwrap();
+        assert_eq!(0xCF, control.encode().data[1]);
+        assert_eq!(None, SwitchBankControl::toggle(&status, 4));
+
+        let control = SwitchBankControl::set_channel(0, 28, true).unwrap();
+        let decoded = SwitchBankControl::get_data(control.encode()).unwrap();
+        assert_eq!(SwitchState::On, decoded.switches[27]);
+        assert_eq!(0x7F, control.encode().data[7]);
+    }
+}
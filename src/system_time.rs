@@ -1,9 +1,14 @@
-use crate::{nmea_frame::SystemTimeFrame, parse, Message, NmeaError};
-use nom::IResult;
+use crate::{
+    date::{Date, DateTime, Time},
+    field_value::FieldValue,
+    nmea_frame::SystemTimeFrame,
+    pgn_fields::SystemTimeFields,
+    Message, NmeaError,
+};
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
-use parse::{take_byte, take_nibble, take_u16, take_u32, BitInput};
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(FromPrimitive, Debug)]
 pub enum SystemTimeSource {
     GPS,
@@ -14,55 +19,33 @@ pub enum SystemTimeSource {
     LocalCrystalClock,
 }
 
-pub fn parse_system_time(i: BitInput) -> IResult<BitInput, SystemTime> {
-    let (i, sid) = take_byte(i)?;
-    let (i, source_raw) = take_nibble(i)?;
-    let (i, _) = take_nibble(i)?;
-    let (i, date) = take_u16(i)?;
-    let (i, time) = take_u32(i)?;
-
-    let source = FromPrimitive::from_u8(source_raw);
-
-    let system_time = SystemTime {
-        sid,
-        source,
-        date,
-        time: Time::new(time),
-    };
-    Ok((i, system_time))
-}
-
 impl Message<SystemTime, SystemTimeFrame> for SystemTime {
     fn get_data(frame: SystemTimeFrame) -> Result<SystemTime, NmeaError> {
-        let data = frame.data;
-        let parse_result: IResult<&[u8], SystemTime> = nom::bits::bits(parse_system_time)(&data);
-        match parse_result {
-            Ok((_, system_time)) => Ok(system_time),
-            Err(_e) => Err(NmeaError::ParseError),
-        }
+        let fields = SystemTimeFields::get_data(&frame.data)?;
+        Ok(SystemTime {
+            sid: fields.sid.map(|sid| sid as u8),
+            source: fields.source.value().and_then(FromPrimitive::from_f64),
+            date: fields.date.map(|days| Date::new(days as u16)),
+            time: fields.time.map(Time::from_seconds),
+        })
     }
 }
 
 #[derive(Debug)]
-pub struct Time {
-    time: u32,
+pub struct SystemTime {
+    pub sid: FieldValue<u8>,
+    pub source: Option<SystemTimeSource>,
+    pub date: FieldValue<Date>,
+    pub time: FieldValue<Time>,
 }
 
-impl Time {
-    pub fn new(time: u32) -> Time {
-        Time { time }
+impl SystemTime {
+    pub fn get_date_time(&self) -> Option<DateTime> {
+        Some(DateTime {
+            date: self.date.value()?,
+            time: self.time.value()?,
+        })
     }
-    pub fn get_seconds(&self) -> u32 {
-        self.time / 10000
-    }
-}
-
-#[derive(Debug)]
-pub struct SystemTime {
-    pub sid: u8,
-    pub source: Option<SystemTimeSource>,
-    pub date: u16,
-    pub time: Time,
 }
 
 #[cfg(test)]
@@ -82,7 +65,7 @@ mod tests {
             data: system_time_data,
         });
         let data = parsed_data.unwrap();
-        assert_eq!(19625, data.date);
-        assert_eq!(2803, data.time.get_seconds());
+        assert_eq!(19625, data.date.value().unwrap().get_days_since_epoch());
+        assert_eq!(2803, data.time.value().unwrap().get_seconds());
     }
 }
@@ -0,0 +1,962 @@
+//! ISO 11783-3 / J1939-21 transport protocol, used for messages longer than a
+//! single frame that are not sent as fast packets.
+//!
+//! A message is announced on TP.CM (PGN 60416), either broadcast with BAM or
+//! to one device with RTS/CTS flow control, and its data follows on TP.DT
+//! (PGN 60160) seven bytes at a time. [`TransportProtocol`] keeps track of
+//! the sessions in both directions. It does no I/O: frames and the current
+//! time go in, and the frames to send and the completed messages come out.
+
+use core::time::Duration;
+
+use nom::IResult;
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use crate::{
+    nmea_frame::ISOTransportProtocolConnectionManagementFrame,
+    parse::{take_byte, take_u24, BitInput},
+    pgn::PGN,
+    write::FieldWriter,
+    Encode, Message, NmeaError,
+};
+
+/// Largest message the transport protocol can carry: 255 packets of 7 bytes.
+pub const MAX_MESSAGE_LENGTH: usize = 1785;
+
+pub const GLOBAL_ADDRESS: u8 = 255;
+
+/// Longest gap between two data packets.
+const T1: Duration = Duration::from_millis(750);
+/// Longest wait for data after sending a CTS.
+const T2: Duration = Duration::from_millis(1250);
+/// Longest wait for a CTS or the end of message acknowledgement after
+/// sending.
+const T3: Duration = Duration::from_millis(1250);
+/// Longest a receiver may hold the connection open with a zero packet CTS.
+const T4: Duration = Duration::from_millis(1050);
+
+#[derive(FromPrimitive, Debug, PartialEq, Clone, Copy)]
+pub enum AbortReason {
+    AlreadyInSession = 1,
+    ResourcesNeeded = 2,
+    Timeout = 3,
+    CTSWhileTransferring = 4,
+    RetransmitLimitReached = 5,
+    UnexpectedDataTransfer = 6,
+    BadSequenceNumber = 7,
+    DuplicateSequenceNumber = 8,
+    MessageTooLarge = 9,
+}
+
+/// PGN 60416
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ConnectionManagement {
+    RequestToSend {
+        size: u16,
+        packets: u8,
+        /// 255 when the sender has no limit.
+        max_packets_per_cts: u8,
+        pgn: u32,
+    },
+    ClearToSend {
+        /// Zero asks the sender to wait.
+        packets: u8,
+        next_packet: u8,
+        pgn: u32,
+    },
+    EndOfMessageAcknowledgement {
+        size: u16,
+        packets: u8,
+        pgn: u32,
+    },
+    BroadcastAnnounce {
+        size: u16,
+        packets: u8,
+        pgn: u32,
+    },
+    Abort {
+        reason: Option<AbortReason>,
+        pgn: u32,
+    },
+}
+
+impl ConnectionManagement {
+    pub fn get_pgn(&self) -> u32 {
+        match *self {
+            ConnectionManagement::RequestToSend { pgn, .. }
+            | ConnectionManagement::ClearToSend { pgn, .. }
+            | ConnectionManagement::EndOfMessageAcknowledgement { pgn, .. }
+            | ConnectionManagement::BroadcastAnnounce { pgn, .. }
+            | ConnectionManagement::Abort { pgn, .. } => pgn,
+        }
+    }
+}
+
+fn parse_connection_management(i: BitInput) -> IResult<BitInput, Option<ConnectionManagement>> {
+    let (i, control) = take_byte(i)?;
+    let (i, first) = take_byte(i)?;
+    let (i, second) = take_byte(i)?;
+    let (i, third) = take_byte(i)?;
+    let (i, fourth) = take_byte(i)?;
+    let (i, pgn) = take_u24(i)?;
+
+    let size = u16::from_le_bytes([first, second]);
+    let connection_management = match control {
+        16 => Some(ConnectionManagement::RequestToSend {
+            size,
+            packets: third,
+            max_packets_per_cts: fourth,
+            pgn,
+        }),
+        17 => Some(ConnectionManagement::ClearToSend {
+            packets: first,
+            next_packet: second,
+            pgn,
+        }),
+        19 => Some(ConnectionManagement::EndOfMessageAcknowledgement {
+            size,
+            packets: third,
+            pgn,
+        }),
+        32 => Some(ConnectionManagement::BroadcastAnnounce {
+            size,
+            packets: third,
+            pgn,
+        }),
+        255 => Some(ConnectionManagement::Abort {
+            reason: FromPrimitive::from_u8(first),
+            pgn,
+        }),
+        _ => None,
+    };
+    Ok((i, connection_management))
+}
+
+impl Message<ConnectionManagement, ISOTransportProtocolConnectionManagementFrame>
+    for ConnectionManagement
+{
+    fn get_data(
+        frame: ISOTransportProtocolConnectionManagementFrame,
+    ) -> Result<ConnectionManagement, NmeaError> {
+        let data = frame.data;
+        let parse_result: IResult<&[u8], Option<ConnectionManagement>> =
+            nom::bits::bits(parse_connection_management)(&data);
+        match parse_result {
+            Ok((_, Some(connection_management))) => Ok(connection_management),
+            Ok((_, None)) => Err(NmeaError::NotImplemented),
+            Err(_e) => Err(NmeaError::ParseError),
+        }
+    }
+}
+
+impl Encode<ISOTransportProtocolConnectionManagementFrame> for ConnectionManagement {
+    fn encode(&self) -> ISOTransportProtocolConnectionManagementFrame {
+        let mut writer = FieldWriter::new();
+        match *self {
+            ConnectionManagement::RequestToSend {
+                size,
+                packets,
+                max_packets_per_cts,
+                pgn,
+            } => {
+                writer.put_u8(16);
+                writer.put_u16(size);
+                writer.put_u8(packets);
+                writer.put_u8(max_packets_per_cts);
+                writer.put_u24(pgn);
+            }
+            ConnectionManagement::ClearToSend {
+                packets,
+                next_packet,
+                pgn,
+            } => {
+                writer.put_u8(17);
+                writer.put_u8(packets);
+                writer.put_u8(next_packet);
+                writer.put_u16(0xFFFF);
+                writer.put_u24(pgn);
+            }
+            ConnectionManagement::EndOfMessageAcknowledgement { size, packets, pgn } => {
+                writer.put_u8(19);
+                writer.put_u16(size);
+                writer.put_u8(packets);
+                writer.put_u8(0xFF);
+                writer.put_u24(pgn);
+            }
+            ConnectionManagement::BroadcastAnnounce { size, packets, pgn } => {
+                writer.put_u8(32);
+                writer.put_u16(size);
+                writer.put_u8(packets);
+                writer.put_u8(0xFF);
+                writer.put_u24(pgn);
+            }
+            ConnectionManagement::Abort { reason, pgn } => {
+                writer.put_u8(255);
+                writer.put_u8(reason.map_or(0xFF, |reason| reason as u8));
+                writer.put_u16(0xFFFF);
+                writer.put_u8(0xFF);
+                writer.put_u24(pgn);
+            }
+        }
+        ISOTransportProtocolConnectionManagementFrame {
+            data: writer.into_frame(),
+        }
+    }
+}
+
+/// A frame for the caller to put on the bus, from our own address.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct OutgoingFrame {
+    pub pgn: PGN,
+    pub destination: u8,
+    pub data: [u8; 8],
+}
+
+impl OutgoingFrame {
+    fn connection_management(destination: u8, message: ConnectionManagement) -> Self {
+        OutgoingFrame {
+            pgn: PGN::ISOTransportProtocolConnectionManagement,
+            destination,
+            data: message.encode().data,
+        }
+    }
+
+    fn data_transfer(destination: u8, sequence: u8, payload: &[u8]) -> Self {
+        let start = (usize::from(sequence) - 1) * 7;
+        let chunk = &payload[start..(start + 7).min(payload.len())];
+        let mut data = [0xFF; 8];
+        data[0] = sequence;
+        data[1..1 + chunk.len()].copy_from_slice(chunk);
+        OutgoingFrame {
+            pgn: PGN::ISOTransportProtocolDataTransfer,
+            destination,
+            data,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum TransportEvent {
+    Send(OutgoingFrame),
+    /// A message has been fully received.
+    Received {
+        source: u8,
+        destination: u8,
+        pgn: u32,
+        data: Vec<u8>,
+    },
+    /// A message we sent has been acknowledged by its destination.
+    Sent {
+        destination: u8,
+        pgn: u32,
+    },
+    Aborted {
+        source: u8,
+        destination: u8,
+        pgn: u32,
+        reason: Option<AbortReason>,
+    },
+}
+
+struct ReceiveSession {
+    source: u8,
+    destination: u8,
+    pgn: u32,
+    size: usize,
+    packets: u8,
+    max_packets_per_cts: u8,
+    next_packet: u8,
+    /// Last packet of the window granted by the latest CTS.
+    window_end: u8,
+    data: Vec<u8>,
+    deadline: Duration,
+}
+
+impl ReceiveSession {
+    fn is_broadcast(&self) -> bool {
+        self.destination == GLOBAL_ADDRESS
+    }
+
+    /// Grants the next window and returns the CTS for it.
+    fn clear_to_send(&mut self, now: Duration) -> OutgoingFrame {
+        let remaining = self.packets - self.next_packet + 1;
+        let window = remaining.min(self.max_packets_per_cts);
+        self.window_end = self.next_packet + (window - 1);
+        self.deadline = now + T2;
+        OutgoingFrame::connection_management(
+            self.source,
+            ConnectionManagement::ClearToSend {
+                packets: window,
+                next_packet: self.next_packet,
+                pgn: self.pgn,
+            },
+        )
+    }
+}
+
+struct TransmitSession {
+    destination: u8,
+    pgn: u32,
+    data: Vec<u8>,
+    deadline: Duration,
+}
+
+fn packet_count(size: usize) -> u8 {
+    size.div_ceil(7) as u8
+}
+
+/// Transport protocol sessions to and from the device at `address`.
+pub struct TransportProtocol {
+    address: u8,
+    receiving: Vec<ReceiveSession>,
+    transmitting: Vec<TransmitSession>,
+}
+
+impl TransportProtocol {
+    pub fn new(address: u8) -> Self {
+        TransportProtocol {
+            address,
+            receiving: Vec::new(),
+            transmitting: Vec::new(),
+        }
+    }
+
+    /// Our source address changed, after losing an address claim.
+    pub fn set_address(&mut self, address: u8) {
+        self.address = address;
+    }
+
+    /// Handles a TP.CM or TP.DT frame from `source` to `destination`. Other
+    /// PGNs and frames addressed to other devices are ignored.
+    pub fn receive(
+        &mut self,
+        source: u8,
+        destination: u8,
+        pgn: u32,
+        data: &[u8; 8],
+        now: Duration,
+    ) -> Vec<TransportEvent> {
+        if destination != self.address && destination != GLOBAL_ADDRESS {
+            return Vec::new();
+        }
+        match FromPrimitive::from_u32(pgn) {
+            Some(PGN::ISOTransportProtocolConnectionManagement) => {
+                let frame = ISOTransportProtocolConnectionManagementFrame { data: *data };
+                match ConnectionManagement::get_data(frame) {
+                    Ok(message) => self.connection_management(source, destination, message, now),
+                    Err(_e) => Vec::new(),
+                }
+            }
+            Some(PGN::ISOTransportProtocolDataTransfer) => {
+                self.data_transfer(source, destination, data, now)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn connection_management(
+        &mut self,
+        source: u8,
+        destination: u8,
+        message: ConnectionManagement,
+        now: Duration,
+    ) -> Vec<TransportEvent> {
+        match message {
+            ConnectionManagement::BroadcastAnnounce { size, packets, pgn } => {
+                if destination != GLOBAL_ADDRESS
+                    || usize::from(size) > MAX_MESSAGE_LENGTH
+                    || packets == 0
+                    || packet_count(usize::from(size)) != packets
+                {
+                    return Vec::new();
+                }
+                self.start_receiving(source, destination, pgn, size, packets, 255, now);
+                Vec::new()
+            }
+            ConnectionManagement::RequestToSend {
+                size,
+                packets,
+                max_packets_per_cts,
+                pgn,
+            } => {
+                if destination == GLOBAL_ADDRESS {
+                    return Vec::new();
+                }
+                if usize::from(size) > MAX_MESSAGE_LENGTH {
+                    let abort = ConnectionManagement::Abort {
+                        reason: Some(AbortReason::MessageTooLarge),
+                        pgn,
+                    };
+                    return vec![TransportEvent::Send(OutgoingFrame::connection_management(
+                        source, abort,
+                    ))];
+                }
+                if packets == 0 || packet_count(usize::from(size)) != packets {
+                    return Vec::new();
+                }
+                let max_packets_per_cts = max_packets_per_cts.max(1);
+                let session = self.start_receiving(
+                    source,
+                    destination,
+                    pgn,
+                    size,
+                    packets,
+                    max_packets_per_cts,
+                    now,
+                );
+                vec![TransportEvent::Send(session.clear_to_send(now))]
+            }
+            ConnectionManagement::ClearToSend {
+                packets,
+                next_packet,
+                pgn,
+            } => {
+                let Some(index) = self.transmit_session(source, pgn) else {
+                    return Vec::new();
+                };
+                let session = &mut self.transmitting[index];
+                if packets == 0 {
+                    session.deadline = now + T4;
+                    return Vec::new();
+                }
+                let last_packet = packet_count(session.data.len());
+                if next_packet == 0 || next_packet > last_packet {
+                    let session = self.transmitting.remove(index);
+                    return self.abort(
+                        self.address,
+                        session.destination,
+                        pgn,
+                        AbortReason::BadSequenceNumber,
+                    );
+                }
+                session.deadline = now + T3;
+                let end = next_packet.saturating_add(packets - 1).min(last_packet);
+                (next_packet..=end)
+                    .map(|sequence| {
+                        TransportEvent::Send(OutgoingFrame::data_transfer(
+                            source,
+                            sequence,
+                            &session.data,
+                        ))
+                    })
+                    .collect()
+            }
+            ConnectionManagement::EndOfMessageAcknowledgement { pgn, .. } => {
+                match self.transmit_session(source, pgn) {
+                    Some(index) => {
+                        self.transmitting.remove(index);
+                        vec![TransportEvent::Sent {
+                            destination: source,
+                            pgn,
+                        }]
+                    }
+                    None => Vec::new(),
+                }
+            }
+            ConnectionManagement::Abort { reason, pgn } => {
+                self.receiving
+                    .retain(|session| !(session.source == source && session.pgn == pgn));
+                self.transmitting
+                    .retain(|session| !(session.destination == source && session.pgn == pgn));
+                vec![TransportEvent::Aborted {
+                    source,
+                    destination,
+                    pgn,
+                    reason,
+                }]
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn start_receiving(
+        &mut self,
+        source: u8,
+        destination: u8,
+        pgn: u32,
+        size: u16,
+        packets: u8,
+        max_packets_per_cts: u8,
+        now: Duration,
+    ) -> &mut ReceiveSession {
+        // Only one transfer at a time between two devices: data packets do
+        // not say which message they belong to.
+        self.receiving
+            .retain(|session| !(session.source == source && session.destination == destination));
+        self.receiving.push(ReceiveSession {
+            source,
+            destination,
+            pgn,
+            size: usize::from(size),
+            packets,
+            max_packets_per_cts,
+            next_packet: 1,
+            window_end: packets,
+            data: Vec::with_capacity(usize::from(packets) * 7),
+            deadline: now + T1,
+        });
+        self.receiving.last_mut().unwrap()
+    }
+
+    fn data_transfer(
+        &mut self,
+        source: u8,
+        destination: u8,
+        data: &[u8; 8],
+        now: Duration,
+    ) -> Vec<TransportEvent> {
+        let Some(index) = self
+            .receiving
+            .iter()
+            .position(|session| session.source == source && session.destination == destination)
+        else {
+            return Vec::new();
+        };
+        let session = &mut self.receiving[index];
+        let sequence = data[0];
+        if sequence != session.next_packet || sequence > session.window_end {
+            let session = self.receiving.remove(index);
+            if session.is_broadcast() {
+                return Vec::new();
+            }
+            let reason = if sequence < session.next_packet {
+                AbortReason::DuplicateSequenceNumber
+            } else {
+                AbortReason::BadSequenceNumber
+            };
+            return self.abort(source, destination, session.pgn, reason);
+        }
+
+        session.data.extend_from_slice(&data[1..]);
+        session.deadline = now + T1;
+
+        // Checked before counting the packet: the last of 255 would
+        // overflow `next_packet`.
+        if sequence == session.packets {
+            let mut session = self.receiving.remove(index);
+            session.data.truncate(session.size);
+            let mut events = Vec::new();
+            if !session.is_broadcast() {
+                events.push(TransportEvent::Send(OutgoingFrame::connection_management(
+                    source,
+                    ConnectionManagement::EndOfMessageAcknowledgement {
+                        size: session.size as u16,
+                        packets: session.packets,
+                        pgn: session.pgn,
+                    },
+                )));
+            }
+            events.push(TransportEvent::Received {
+                source,
+                destination,
+                pgn: session.pgn,
+                data: session.data,
+            });
+            return events;
+        }
+        session.next_packet += 1;
+        if !session.is_broadcast() && sequence == session.window_end {
+            return vec![TransportEvent::Send(session.clear_to_send(now))];
+        }
+        Vec::new()
+    }
+
+    /// Starts sending `data` as `pgn`. Broadcasts go out with BAM and all
+    /// their frames are returned at once; the caller should leave 50 to 200
+    /// ms between them. Messages to a single device start with an RTS and
+    /// continue as the destination sends CTS frames to [`Self::receive`].
+    pub fn send(
+        &mut self,
+        destination: u8,
+        pgn: u32,
+        data: &[u8],
+        now: Duration,
+    ) -> Result<Vec<TransportEvent>, NmeaError> {
+        if data.len() <= 8 || data.len() > MAX_MESSAGE_LENGTH {
+            return Err(NmeaError::InvalidLength);
+        }
+        let size = data.len() as u16;
+        let packets = packet_count(data.len());
+
+        if destination == GLOBAL_ADDRESS {
+            let announce = ConnectionManagement::BroadcastAnnounce { size, packets, pgn };
+            let mut events = vec![TransportEvent::Send(OutgoingFrame::connection_management(
+                destination,
+                announce,
+            ))];
+            events.extend((1..=packets).map(|sequence| {
+                TransportEvent::Send(OutgoingFrame::data_transfer(destination, sequence, data))
+            }));
+            return Ok(events);
+        }
+
+        if self
+            .transmitting
+            .iter()
+            .any(|session| session.destination == destination)
+        {
+            return Err(NmeaError::Busy);
+        }
+        self.transmitting.push(TransmitSession {
+            destination,
+            pgn,
+            data: data.to_vec(),
+            deadline: now + T3,
+        });
+        let request = ConnectionManagement::RequestToSend {
+            size,
+            packets,
+            max_packets_per_cts: 255,
+            pgn,
+        };
+        Ok(vec![TransportEvent::Send(
+            OutgoingFrame::connection_management(destination, request),
+        )])
+    }
+
+    /// Aborts sessions whose peer has gone quiet for too long.
+    pub fn poll(&mut self, now: Duration) -> Vec<TransportEvent> {
+        let mut events = Vec::new();
+        let address = self.address;
+        let (expired, receiving): (Vec<ReceiveSession>, Vec<ReceiveSession>) = self
+            .receiving
+            .drain(..)
+            .partition(|session| now > session.deadline);
+        self.receiving = receiving;
+        for session in expired {
+            if session.is_broadcast() {
+                events.push(TransportEvent::Aborted {
+                    source: session.source,
+                    destination: session.destination,
+                    pgn: session.pgn,
+                    reason: Some(AbortReason::Timeout),
+                });
+            } else {
+                events.extend(self.abort(
+                    session.source,
+                    session.destination,
+                    session.pgn,
+                    AbortReason::Timeout,
+                ));
+            }
+        }
+
+        let (expired, transmitting): (Vec<TransmitSession>, Vec<TransmitSession>) = self
+            .transmitting
+            .drain(..)
+            .partition(|session| now > session.deadline);
+        self.transmitting = transmitting;
+        for session in expired {
+            events.extend(self.abort(
+                address,
+                session.destination,
+                session.pgn,
+                AbortReason::Timeout,
+            ));
+        }
+        events
+    }
+
+    fn transmit_session(&self, destination: u8, pgn: u32) -> Option<usize> {
+        self.transmitting
+            .iter()
+            .position(|session| session.destination == destination && session.pgn == pgn)
+    }
+
+    /// Tells the other end of the transfer from `source` to `destination`
+    /// that it is off, and reports it to the caller.
+    fn abort(
+        &self,
+        source: u8,
+        destination: u8,
+        pgn: u32,
+        reason: AbortReason,
+    ) -> Vec<TransportEvent> {
+        let peer = if source == self.address {
+            destination
+        } else {
+            source
+        };
+        let abort = ConnectionManagement::Abort {
+            reason: Some(reason),
+            pgn,
+        };
+        vec![
+            TransportEvent::Send(OutgoingFrame::connection_management(peer, abort)),
+            TransportEvent::Aborted {
+                source,
+                destination,
+                pgn,
+                reason: Some(reason),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PGN: u32 = 126464;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    /// Delivers every frame sent by `from` to `to`, returning what `to`
+    /// answers and reports.
+    fn deliver(
+        from: u8,
+        events: Vec<TransportEvent>,
+        to: &mut TransportProtocol,
+        now: Duration,
+    ) -> Vec<TransportEvent> {
+        let mut replies = Vec::new();
+        for event in events {
+            if let TransportEvent::Send(frame) = event {
+                replies.extend(to.receive(
+                    from,
+                    frame.destination,
+                    frame.pgn as u32,
+                    &frame.data,
+                    now,
+                ));
+            }
+        }
+        replies
+    }
+
+    #[test]
+    fn reassembles_broadcast() {
+        let payload: Vec<u8> = (0..20).collect();
+        let mut sender = TransportProtocol::new(1);
+        let mut receiver = TransportProtocol::new(2);
+        let frames = sender.send(GLOBAL_ADDRESS, PGN, &payload, ms(0)).unwrap();
+        assert_eq!(4, frames.len());
+
+        let events = deliver(1, frames, &mut receiver, ms(50));
+        assert_eq!(
+            vec![TransportEvent::Received {
+                source: 1,
+                destination: GLOBAL_ADDRESS,
+                pgn: PGN,
+                data: payload,
+            }],
+            events
+        );
+    }
+
+    #[test]
+    fn drops_invalid_broadcast() {
+        let mut receiver = TransportProtocol::new(2);
+        for (size, packets) in [(0, 0), (20, 0), (20, 2), (1786, 255)] {
+            let announce = ConnectionManagement::BroadcastAnnounce {
+                size,
+                packets,
+                pgn: PGN,
+            };
+            let frame = announce.encode();
+            assert!(receiver
+                .receive(1, GLOBAL_ADDRESS, 60416, &frame.data, ms(0))
+                .is_empty());
+        }
+        // No session was started, so none times out
+        assert!(receiver.poll(ms(5000)).is_empty());
+    }
+
+    #[test]
+    fn reassembles_largest_broadcast() {
+        let payload: Vec<u8> = (0..MAX_MESSAGE_LENGTH).map(|byte| byte as u8).collect();
+        let mut sender = TransportProtocol::new(1);
+        let mut receiver = TransportProtocol::new(2);
+        let frames = sender.send(GLOBAL_ADDRESS, PGN, &payload, ms(0)).unwrap();
+        assert_eq!(256, frames.len());
+
+        let events = deliver(1, frames, &mut receiver, ms(50));
+        assert_eq!(
+            vec![TransportEvent::Received {
+                source: 1,
+                destination: GLOBAL_ADDRESS,
+                pgn: PGN,
+                data: payload,
+            }],
+            events
+        );
+    }
+
+    #[test]
+    fn receives_largest_message_with_flow_control() {
+        let payload: Vec<u8> = (0..MAX_MESSAGE_LENGTH).map(|byte| byte as u8).collect();
+        let mut sender = TransportProtocol::new(1);
+        let mut receiver = TransportProtocol::new(2);
+
+        let request = sender.send(2, PGN, &payload, ms(0)).unwrap();
+        let cts = deliver(1, request, &mut receiver, ms(10));
+        let TransportEvent::Send(frame) = cts[0] else {
+            panic!("expected a CTS");
+        };
+        // One window for all 255 packets
+        assert_eq!([17, 255, 1], frame.data[..3]);
+        let data = deliver(2, cts, &mut sender, ms(20));
+        assert_eq!(255, data.len());
+
+        let mut replies = deliver(1, data, &mut receiver, ms(30));
+        assert_eq!(
+            TransportEvent::Received {
+                source: 1,
+                destination: 2,
+                pgn: PGN,
+                data: payload,
+            },
+            replies.pop().unwrap()
+        );
+        let done = deliver(2, replies, &mut sender, ms(40));
+        assert_eq!(
+            vec![TransportEvent::Sent {
+                destination: 2,
+                pgn: PGN,
+            }],
+            done
+        );
+    }
+
+    #[test]
+    fn transfers_with_flow_control() {
+        let payload: Vec<u8> = (0..100).collect();
+        let mut sender = TransportProtocol::new(1);
+        let mut receiver = TransportProtocol::new(2);
+
+        let request = sender.send(2, PGN, &payload, ms(0)).unwrap();
+        let cts = deliver(1, request, &mut receiver, ms(10));
+        assert_eq!(1, cts.len());
+        let data = deliver(2, cts, &mut sender, ms(20));
+        assert_eq!(15, data.len());
+
+        let mut replies = deliver(1, data, &mut receiver, ms(30));
+        let received = replies.pop().unwrap();
+        assert_eq!(
+            TransportEvent::Received {
+                source: 1,
+                destination: 2,
+                pgn: PGN,
+                data: payload,
+            },
+            received
+        );
+        let done = deliver(2, replies, &mut sender, ms(40));
+        assert_eq!(
+            vec![TransportEvent::Sent {
+                destination: 2,
+                pgn: PGN,
+            }],
+            done
+        );
+    }
+
+    #[test]
+    fn grants_windows_of_packets() {
+        let mut receiver = TransportProtocol::new(2);
+        let request = ConnectionManagement::RequestToSend {
+            size: 30,
+            packets: 5,
+            max_packets_per_cts: 2,
+            pgn: PGN,
+        };
+        let events = receiver.receive(1, 2, 60416, &request.encode().data, ms(0));
+        let TransportEvent::Send(cts) = events[0] else {
+            panic!("expected a CTS");
+        };
+        assert_eq!([17, 2, 1, 0xFF, 0xFF, 0x00, 0xEE, 0x01], cts.data);
+
+        let packet = |sequence: u8| [sequence, 0, 0, 0, 0, 0, 0, 0];
+        assert!(receiver.receive(1, 2, 60160, &packet(1), ms(10)).is_empty());
+        let events = receiver.receive(1, 2, 60160, &packet(2), ms(20));
+        let TransportEvent::Send(cts) = events[0] else {
+            panic!("expected a CTS");
+        };
+        assert_eq!([17, 2, 3], cts.data[..3]);
+
+        // Packet 3 is skipped
+        let events = receiver.receive(1, 2, 60160, &packet(4), ms(30));
+        assert_eq!(
+            TransportEvent::Aborted {
+                source: 1,
+                destination: 2,
+                pgn: PGN,
+                reason: Some(AbortReason::BadSequenceNumber),
+            },
+            events[1]
+        );
+    }
+
+    #[test]
+    fn times_out_silent_sessions() {
+        let mut sender = TransportProtocol::new(1);
+        sender.send(2, PGN, &[0; 20], ms(0)).unwrap();
+        assert!(matches!(
+            sender.send(2, PGN, &[0; 20], ms(0)),
+            Err(NmeaError::Busy)
+        ));
+        assert!(sender.poll(ms(1000)).is_empty());
+
+        let events = sender.poll(ms(1300));
+        let TransportEvent::Send(abort) = events[0] else {
+            panic!("expected an abort");
+        };
+        assert_eq!(2, abort.destination);
+        assert_eq!(
+            ConnectionManagement::Abort {
+                reason: Some(AbortReason::Timeout),
+                pgn: PGN,
+            },
+            ConnectionManagement::get_data(ISOTransportProtocolConnectionManagementFrame {
+                data: abort.data
+            })
+            .unwrap()
+        );
+        assert_eq!(
+            TransportEvent::Aborted {
+                source: 1,
+                destination: 2,
+                pgn: PGN,
+                reason: Some(AbortReason::Timeout),
+            },
+            events[1]
+        );
+        assert!(sender.send(2, PGN, &[0; 20], ms(1300)).is_ok());
+    }
+
+    #[test]
+    fn validates_request_to_send() {
+        let mut receiver = TransportProtocol::new(2);
+        let request = |size: u16, packets: u8| ConnectionManagement::RequestToSend {
+            size,
+            packets,
+            max_packets_per_cts: 255,
+            pgn: PGN,
+        };
+
+        // Packet counts that do not match the size are ignored
+        for (size, packets) in [(0, 0), (30, 0), (30, 4), (30, 6)] {
+            let frame = request(size, packets).encode();
+            assert!(receiver.receive(1, 2, 60416, &frame.data, ms(0)).is_empty());
+        }
+
+        let frame = request(1786, 255).encode();
+        let events = receiver.receive(1, 2, 60416, &frame.data, ms(0));
+        let TransportEvent::Send(abort) = events[0] else {
+            panic!("expected an abort");
+        };
+        assert_eq!(
+            ConnectionManagement::Abort {
+                reason: Some(AbortReason::MessageTooLarge),
+                pgn: PGN,
+            },
+            ConnectionManagement::get_data(ISOTransportProtocolConnectionManagementFrame {
+                data: abort.data
+            })
+            .unwrap()
+        );
+    }
+}
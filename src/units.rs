@@ -0,0 +1,435 @@
+//! Physical quantities decoded from NMEA 2000 fields. Each type stores its
+//! value in SI units, the resolution of the field is applied while decoding.
+
+const METERS_PER_NAUTICAL_MILE: f64 = 1852.0;
+const KELVIN_OFFSET: f32 = 273.15;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Speed {
+    meters_per_second: f32,
+}
+
+impl Speed {
+    pub fn from_meters_per_second(meters_per_second: f32) -> Self {
+        Speed { meters_per_second }
+    }
+
+    pub fn get_meters_per_second(&self) -> f32 {
+        self.meters_per_second
+    }
+
+    pub fn get_knots(&self) -> f32 {
+        self.meters_per_second * 3600.0 / METERS_PER_NAUTICAL_MILE as f32
+    }
+
+    pub fn get_kilometers_per_hour(&self) -> f32 {
+        self.meters_per_second * 3.6
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Distance {
+    meters: f64,
+}
+
+impl Distance {
+    pub fn from_meters(meters: f64) -> Self {
+        Distance { meters }
+    }
+
+    pub fn get_meters(&self) -> f64 {
+        self.meters
+    }
+
+    pub fn get_nautical_miles(&self) -> f64 {
+        self.meters / METERS_PER_NAUTICAL_MILE
+    }
+
+    pub fn get_kilometers(&self) -> f64 {
+        self.meters / 1000.0
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Temperature {
+    kelvin: f32,
+}
+
+impl Temperature {
+    pub fn from_kelvin(kelvin: f32) -> Self {
+        Temperature { kelvin }
+    }
+
+    pub fn get_kelvin(&self) -> f32 {
+        self.kelvin
+    }
+
+    pub fn get_celsius(&self) -> f32 {
+        self.kelvin - KELVIN_OFFSET
+    }
+
+    pub fn get_fahrenheit(&self) -> f32 {
+        self.get_celsius() * 9.0 / 5.0 + 32.0
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Pressure {
+    pascals: f32,
+}
+
+impl Pressure {
+    pub fn from_pascals(pascals: f32) -> Self {
+        Pressure { pascals }
+    }
+
+    pub fn get_pascals(&self) -> f32 {
+        self.pascals
+    }
+
+    pub fn get_hectopascals(&self) -> f32 {
+        self.pascals / 100.0
+    }
+
+    pub fn get_bar(&self) -> f32 {
+        self.pascals / 100_000.0
+    }
+
+    pub fn get_psi(&self) -> f32 {
+        self.pascals / 6894.757
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Voltage {
+    volts: f32,
+}
+
+impl Voltage {
+    pub fn from_volts(volts: f32) -> Self {
+        Voltage { volts }
+    }
+
+    pub fn get_volts(&self) -> f32 {
+        self.volts
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Current {
+    amperes: f32,
+}
+
+impl Current {
+    pub fn from_amperes(amperes: f32) -> Self {
+        Current { amperes }
+    }
+
+    pub fn get_amperes(&self) -> f32 {
+        self.amperes
+    }
+}
+
+/// Real, reactive or apparent power. Watts, vars and volt-amperes share
+/// the same scale.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Power {
+    watts: f32,
+}
+
+impl Power {
+    pub fn from_watts(watts: f32) -> Self {
+        Power { watts }
+    }
+
+    pub fn get_watts(&self) -> f32 {
+        self.watts
+    }
+
+    pub fn get_kilowatts(&self) -> f32 {
+        self.watts / 1000.0
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Frequency {
+    hertz: f32,
+}
+
+impl Frequency {
+    pub fn from_hertz(hertz: f32) -> Self {
+        Frequency { hertz }
+    }
+
+    pub fn get_hertz(&self) -> f32 {
+        self.hertz
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Volume {
+    liters: f64,
+}
+
+impl Volume {
+    pub fn from_liters(liters: f64) -> Self {
+        Volume { liters }
+    }
+
+    pub fn get_liters(&self) -> f64 {
+        self.liters
+    }
+
+    pub fn get_us_gallons(&self) -> f64 {
+        self.liters / 3.785412
+    }
+}
+
+/// Volume flow, such as an engine's fuel rate.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct FlowRate {
+    liters_per_hour: f32,
+}
+
+impl FlowRate {
+    pub fn from_liters_per_hour(liters_per_hour: f32) -> Self {
+        FlowRate { liters_per_hour }
+    }
+
+    pub fn get_liters_per_hour(&self) -> f32 {
+        self.liters_per_hour
+    }
+
+    pub fn get_us_gallons_per_hour(&self) -> f32 {
+        self.liters_per_hour / 3.785412
+    }
+}
+
+/// Rate of change of a heading or an angle.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct AngularRate {
+    radians_per_second: f64,
+}
+
+impl AngularRate {
+    pub fn from_radians_per_second(radians_per_second: f64) -> Self {
+        AngularRate { radians_per_second }
+    }
+
+    pub fn get_radians_per_second(&self) -> f64 {
+        self.radians_per_second
+    }
+
+    pub fn get_degrees_per_minute(&self) -> f64 {
+        self.radians_per_second.to_degrees() * 60.0
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct RotationalSpeed {
+    revolutions_per_minute: f32,
+}
+
+impl RotationalSpeed {
+    pub fn from_revolutions_per_minute(revolutions_per_minute: f32) -> Self {
+        RotationalSpeed {
+            revolutions_per_minute,
+        }
+    }
+
+    pub fn get_revolutions_per_minute(&self) -> f32 {
+        self.revolutions_per_minute
+    }
+}
+
+/// Latitude in degrees, positive north of the equator.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Latitude {
+    degrees: f64,
+}
+
+impl Latitude {
+    /// Latitude fields are signed 32 bit values with a resolution of 1e-7
+    /// degrees.
+    pub fn new(value: i32) -> Self {
+        Latitude {
+            degrees: f64::from(value) / 1e7,
+        }
+    }
+
+    pub fn from_degrees(degrees: f64) -> Self {
+        Latitude { degrees }
+    }
+
+    pub fn get_degrees(&self) -> f64 {
+        self.degrees
+    }
+
+    pub fn get_radians(&self) -> f64 {
+        self.degrees.to_radians()
+    }
+}
+
+/// Longitude in degrees, positive east of Greenwich.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Longitude {
+    degrees: f64,
+}
+
+impl Longitude {
+    /// Longitude fields are signed 32 bit values with a resolution of 1e-7
+    /// degrees.
+    pub fn new(value: i32) -> Self {
+        Longitude {
+            degrees: f64::from(value) / 1e7,
+        }
+    }
+
+    pub fn from_degrees(degrees: f64) -> Self {
+        Longitude { degrees }
+    }
+
+    pub fn get_degrees(&self) -> f64 {
+        self.degrees
+    }
+
+    pub fn get_radians(&self) -> f64 {
+        self.degrees.to_radians()
+    }
+}
+
+#[cfg(feature = "uom")]
+mod uom_conversions {
+    use super::*;
+    use crate::rad::Rad;
+    use uom::si::{
+        angle::{degree, radian},
+        angular_velocity::{radian_per_second, revolution_per_minute},
+        electric_current::ampere,
+        electric_potential::volt,
+        f32::{
+            Angle, AngularVelocity, ElectricCurrent, ElectricPotential, Frequency as UomFrequency,
+            Power as UomPower, Pressure as UomPressure, ThermodynamicTemperature, Velocity,
+            VolumeRate,
+        },
+        f64::{
+            Angle as Angle64, AngularVelocity as AngularVelocity64, Length, Volume as UomVolume,
+        },
+        frequency::hertz,
+        length::meter,
+        power::watt,
+        pressure::pascal,
+        thermodynamic_temperature::kelvin,
+        velocity::meter_per_second,
+        volume::liter,
+        volume_rate::liter_per_minute,
+    };
+
+    impl From<Speed> for Velocity {
+        fn from(speed: Speed) -> Self {
+            Velocity::new::<meter_per_second>(speed.get_meters_per_second())
+        }
+    }
+
+    impl From<Distance> for Length {
+        fn from(distance: Distance) -> Self {
+            Length::new::<meter>(distance.get_meters())
+        }
+    }
+
+    impl From<Temperature> for ThermodynamicTemperature {
+        fn from(temperature: Temperature) -> Self {
+            ThermodynamicTemperature::new::<kelvin>(temperature.get_kelvin())
+        }
+    }
+
+    impl From<Pressure> for UomPressure {
+        fn from(pressure: Pressure) -> Self {
+            UomPressure::new::<pascal>(pressure.get_pascals())
+        }
+    }
+
+    impl From<Voltage> for ElectricPotential {
+        fn from(voltage: Voltage) -> Self {
+            ElectricPotential::new::<volt>(voltage.get_volts())
+        }
+    }
+
+    impl From<Current> for ElectricCurrent {
+        fn from(current: Current) -> Self {
+            ElectricCurrent::new::<ampere>(current.get_amperes())
+        }
+    }
+
+    impl From<Power> for UomPower {
+        fn from(power: Power) -> Self {
+            UomPower::new::<watt>(power.get_watts())
+        }
+    }
+
+    impl From<Frequency> for UomFrequency {
+        fn from(frequency: Frequency) -> Self {
+            UomFrequency::new::<hertz>(frequency.get_hertz())
+        }
+    }
+
+    impl From<Volume> for UomVolume {
+        fn from(volume: Volume) -> Self {
+            UomVolume::new::<liter>(volume.get_liters())
+        }
+    }
+
+    impl From<FlowRate> for VolumeRate {
+        fn from(flow_rate: FlowRate) -> Self {
+            VolumeRate::new::<liter_per_minute>(flow_rate.get_liters_per_hour() / 60.0)
+        }
+    }
+
+    impl From<AngularRate> for AngularVelocity64 {
+        fn from(rate: AngularRate) -> Self {
+            AngularVelocity64::new::<radian_per_second>(rate.get_radians_per_second())
+        }
+    }
+
+    impl From<RotationalSpeed> for AngularVelocity {
+        fn from(speed: RotationalSpeed) -> Self {
+            AngularVelocity::new::<revolution_per_minute>(speed.get_revolutions_per_minute())
+        }
+    }
+
+    impl From<Rad> for Angle {
+        fn from(rad: Rad) -> Self {
+            Angle::new::<radian>(rad.get_radians())
+        }
+    }
+
+    impl From<Latitude> for Angle64 {
+        fn from(latitude: Latitude) -> Self {
+            Angle64::new::<degree>(latitude.get_degrees())
+        }
+    }
+
+    impl From<Longitude> for Angle64 {
+        fn from(longitude: Longitude) -> Self {
+            Angle64::new::<degree>(longitude.get_degrees())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_units() {
+        assert_eq!(
+            10.0,
+            Speed::from_meters_per_second(5.144444).get_knots().round()
+        );
+        assert_eq!(1.0, Distance::from_meters(1852.0).get_nautical_miles());
+        assert_eq!(20.0, Temperature::from_kelvin(293.15).get_celsius().round());
+        assert_eq!(1013.25, Pressure::from_pascals(101325.0).get_hectopascals());
+        assert_eq!(3.5, Power::from_watts(3500.0).get_kilowatts());
+        assert_eq!(52.3702157, Latitude::new(523702157).get_degrees());
+    }
+}
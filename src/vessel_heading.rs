@@ -1,12 +1,9 @@
 use crate::nmea_frame::VesselHeadingFrame;
-use nom::IResult;
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 
 use crate::{
-    parse::{take_byte, take_i16, take_two_bits, take_u16, BitInput},
-    rad::Rad,
-    Message, NmeaError,
+    field_value::FieldValue, pgn_fields::VesselHeadingFields, rad::Rad, Message, NmeaError,
 };
 
 #[derive(FromPrimitive, Debug, PartialEq)]
@@ -16,53 +13,56 @@ pub enum DirectionReference {
     Error = 3,
 }
 
+impl DirectionReference {
+    /// Reads a two bit reference field, where all ones is `Error`.
+    pub(crate) fn from_field(reference: FieldValue<f64>) -> Option<DirectionReference> {
+        FromPrimitive::from_f64(reference.value().unwrap_or(3.0))
+    }
+}
+
 #[derive(Debug)]
 pub struct VesselHeading {
-    pub sid: u8,
-    pub heading: Rad,
-    pub deviation: Rad,
-    pub variation: Rad,
+    pub sid: FieldValue<u8>,
+    pub heading: FieldValue<Rad>,
+    pub deviation: FieldValue<Rad>,
+    pub variation: FieldValue<Rad>,
     pub reference: Option<DirectionReference>,
 }
 
 impl VesselHeading {
     pub fn new() -> Self {
         VesselHeading {
-            sid: 0,
+            sid: FieldValue::Value(0),
             reference: Some(DirectionReference::True),
-            heading: Rad::new(0),
-            deviation: Rad::from_i16(0),
-            variation: Rad::from_i16(0),
+            heading: FieldValue::Value(Rad::new(0)),
+            deviation: FieldValue::Value(Rad::from_i16(0)),
+            variation: FieldValue::Value(Rad::from_i16(0)),
         }
     }
 }
 
-fn parse_vessel_heading(i: BitInput) -> IResult<BitInput, VesselHeading> {
-    let (i, sid) = take_byte(i)?;
-    let (i, heading) = take_u16(i)?;
-    let (i, deviation) = take_i16(i)?;
-    let (i, variation) = take_i16(i)?;
-    let (i, reference) = take_two_bits(i)?;
-
-    let system_time = VesselHeading {
-        sid,
-        heading: Rad::new(heading),
-        deviation: Rad::from_i16(deviation),
-        variation: Rad::from_i16(variation),
-        reference: FromPrimitive::from_u8(reference),
-    };
-    Ok((i, system_time))
+impl Default for VesselHeading {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Message<VesselHeading, VesselHeadingFrame> for VesselHeading {
     fn get_data(frame: VesselHeadingFrame) -> Result<VesselHeading, NmeaError> {
-        let data = frame.data;
-        let parse_result: IResult<&[u8], VesselHeading> =
-            nom::bits::bits(parse_vessel_heading)(&data);
-        match parse_result {
-            Ok((_, system_time)) => Ok(system_time),
-            Err(_e) => Err(NmeaError::ParseError),
-        }
+        let fields = VesselHeadingFields::get_data(&frame.data)?;
+        Ok(VesselHeading {
+            sid: fields.sid.map(|sid| sid as u8),
+            heading: fields
+                .heading
+                .map(|heading| Rad::from_radians(heading as f32)),
+            deviation: fields
+                .deviation
+                .map(|deviation| Rad::from_radians(deviation as f32)),
+            variation: fields
+                .variation
+                .map(|variation| Rad::from_radians(variation as f32)),
+            reference: DirectionReference::from_field(fields.reference),
+        })
     }
 }
 
@@ -77,8 +77,8 @@ mod tests {
             data: vessel_heading_data,
         });
         let data = parsed_data.unwrap();
-        assert_eq!(6.5534997, data.heading.get_radians());
-        assert_eq!(3.2767, data.deviation.get_radians());
-        assert_eq!(0.1721, data.variation.get_radians());
+        assert_eq!(FieldValue::NotAvailable, data.heading);
+        assert_eq!(FieldValue::NotAvailable, data.deviation);
+        assert_eq!(0.1721, data.variation.value().unwrap().get_radians());
     }
 }
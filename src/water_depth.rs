@@ -0,0 +1,60 @@
+use crate::{
+    field_value::FieldValue, nmea_frame::WaterDepthFrame, pgn_fields::WaterDepthFields,
+    units::Distance, Message, NmeaError,
+};
+
+/// PGN 128267
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct WaterDepth {
+    pub sid: FieldValue<u8>,
+    /// Depth below the transducer.
+    pub depth: FieldValue<Distance>,
+    /// Distance from the transducer to the waterline when positive, or to the
+    /// keel when negative.
+    pub offset: FieldValue<Distance>,
+    /// Maximum depth the transducer can measure.
+    pub range: FieldValue<Distance>,
+}
+
+impl WaterDepth {
+    /// Depth below the waterline or the keel, depending on the offset.
+    pub fn get_depth_with_offset(&self) -> Option<Distance> {
+        let offset = self
+            .offset
+            .value()
+            .map_or(0.0, |offset| offset.get_meters());
+        self.depth
+            .value()
+            .map(|depth| Distance::from_meters(depth.get_meters() + offset))
+    }
+}
+
+impl Message<WaterDepth, WaterDepthFrame> for WaterDepth {
+    fn get_data(frame: WaterDepthFrame) -> Result<WaterDepth, NmeaError> {
+        let fields = WaterDepthFields::get_data(&frame.data)?;
+        Ok(WaterDepth {
+            sid: fields.sid.map(|sid| sid as u8),
+            depth: fields.depth.map(Distance::from_meters),
+            offset: fields.offset.map(Distance::from_meters),
+            range: fields.range.map(Distance::from_meters),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_water_depth() {
+        let data = [12, 0xE2, 0x04, 0, 0, 0x0C, 0xFE, 10];
+        let water_depth = WaterDepth::get_data(WaterDepthFrame { data }).unwrap();
+        assert_eq!(12.5, water_depth.depth.value().unwrap().get_meters());
+        assert_eq!(-0.5, water_depth.offset.value().unwrap().get_meters());
+        assert_eq!(100.0, water_depth.range.value().unwrap().get_meters());
+        assert_eq!(
+            12.0,
+            water_depth.get_depth_with_offset().unwrap().get_meters()
+        );
+    }
+}
@@ -0,0 +1,135 @@
+/// Serializes fields little endian, the byte order used on the bus.
+pub struct FieldWriter {
+    data: Vec<u8>,
+}
+
+impl FieldWriter {
+    pub fn new() -> Self {
+        FieldWriter { data: Vec::new() }
+    }
+
+    pub fn put_u8(&mut self, value: u8) {
+        self.data.push(value);
+    }
+
+    pub fn put_u16(&mut self, value: u16) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// Writes the low three bytes of `value`.
+    pub fn put_u24(&mut self, value: u32) {
+        self.data.extend_from_slice(&value.to_le_bytes()[..3]);
+    }
+
+    pub fn put_u32(&mut self, value: u32) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// A fixed length ASCII field, truncated or padded with 0xFF.
+    pub fn put_fixed_string(&mut self, text: &str, length: usize) {
+        let bytes = text.as_bytes();
+        let used = bytes.len().min(length);
+        self.data.extend_from_slice(&bytes[..used]);
+        self.data.resize(self.data.len() + length - used, 0xFF);
+    }
+
+    /// A variable length string, prefixed by its length and encoding. ASCII
+    /// text is sent as is, anything else as UTF-16LE. Text that does not fit
+    /// in 253 bytes is cut off at a whole character.
+    pub fn put_lau_string(&mut self, text: &str) {
+        let mut bytes = Vec::new();
+        let encoding = if text.is_ascii() {
+            bytes.extend_from_slice(&text.as_bytes()[..text.len().min(253)]);
+            1
+        } else {
+            let mut units = [0; 2];
+            for c in text.chars() {
+                let encoded = c.encode_utf16(&mut units);
+                if bytes.len() + encoded.len() * 2 > 253 {
+                    break;
+                }
+                for unit in encoded {
+                    bytes.extend_from_slice(&unit.to_le_bytes());
+                }
+            }
+            0
+        };
+        self.data.push(bytes.len() as u8 + 2);
+        self.data.push(encoding);
+        self.data.extend_from_slice(&bytes);
+    }
+
+    pub fn into_payload(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Pads the written fields with 0xFF up to a single 8 byte frame.
+    /// Writing more than 8 bytes is a bug in the encoder and panics.
+    pub fn into_frame(self) -> [u8; 8] {
+        assert!(
+            self.data.len() <= 8,
+            "{} bytes written for a single frame message",
+            self.data.len()
+        );
+        let mut frame = [0xFF; 8];
+        frame[..self.data.len()].copy_from_slice(&self.data);
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pads_frame() {
+        let mut writer = FieldWriter::new();
+        writer.put_u16(0x1234);
+        assert_eq!(
+            [0x34, 0x12, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+            writer.into_frame()
+        );
+    }
+
+    #[test]
+    fn round_trips_lau_strings() {
+        let read = |data: &[u8]| {
+            let parse_result: nom::IResult<&[u8], String> =
+                nom::bits::bits(crate::parse::take_lau_string)(data);
+            parse_result.unwrap().1
+        };
+
+        let mut writer = FieldWriter::new();
+        writer.put_lau_string("Zürich");
+        let data = writer.into_payload();
+        assert_eq!(
+            vec![14, 0, b'Z', 0, 0xFC, 0, b'r', 0, b'i', 0, b'c', 0, b'h', 0],
+            data
+        );
+        assert_eq!("Zürich", read(&data));
+
+        // Cut off before a character that would not fit whole
+        let text = "🌊".repeat(64);
+        let mut writer = FieldWriter::new();
+        writer.put_lau_string(&text);
+        let data = writer.into_payload();
+        assert_eq!(254, data[0]);
+        assert_eq!("🌊".repeat(63), read(&data));
+
+        let mut writer = FieldWriter::new();
+        writer.put_lau_string(&"A".repeat(300));
+        let data = writer.into_payload();
+        assert_eq!([255, 1], data[..2]);
+        assert_eq!("A".repeat(253), read(&data));
+    }
+
+    #[test]
+    #[should_panic(expected = "9 bytes written for a single frame message")]
+    fn rejects_more_than_a_frame() {
+        let mut writer = FieldWriter::new();
+        writer.put_u32(0);
+        writer.put_u32(0);
+        writer.put_u8(0);
+        writer.into_frame();
+    }
+}